@@ -0,0 +1,269 @@
+use crate::*;
+
+/// What happens to a gene that ends up outside of its range.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoundaryPolicy {
+    /// Gene is moved to the nearest end of its range.
+    #[default]
+    Clamp,
+    /// Gene bounces off the end of its range, as if it were a mirror.
+    Reflect,
+    /// Gene re-enters the range from its other end.
+    Wrap,
+}
+
+/// Allowed `(min, max)` range of each gene.
+///
+/// Integer genes get rounded back into their range; a range that contains
+/// no integer at all (e.g. `(0.4, 0.6)`) can't be met by them, so they end
+/// up at the integer nearest to its middle instead.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneBounds {
+    ranges: Ranges,
+    policy: BoundaryPolicy,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Ranges {
+    Uniform(f32, f32),
+    PerGene(Vec<(f32, f32)>),
+}
+
+impl GeneBounds {
+    /// Creates bounds with a separate range for each gene; chromosomes
+    /// they're applied to must have exactly `ranges.len()` genes.
+    pub fn new(ranges: Vec<(f32, f32)>) -> Self {
+        assert!(ranges.iter().all(|(min, max)| min <= max));
+
+        Self { ranges: Ranges::PerGene(ranges), policy: Default::default() }
+    }
+
+    /// Creates bounds with the same range for all genes, no matter how many
+    /// of them there are.
+    pub fn uniform(min: f32, max: f32) -> Self {
+        assert!(min <= max);
+
+        Self { ranges: Ranges::Uniform(min, max), policy: Default::default() }
+    }
+
+    pub fn with_policy(mut self, policy: BoundaryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn policy(&self) -> BoundaryPolicy {
+        self.policy
+    }
+
+    /// Returns `(min, max)` range of given gene.
+    pub fn range(&self, gene: usize) -> (f32, f32) {
+        match &self.ranges {
+            Ranges::Uniform(min, max) => (*min, *max),
+            Ranges::PerGene(ranges) => ranges[gene],
+        }
+    }
+
+    pub fn contains<G: Gene>(&self, chromosome: &Chromosome<G>) -> bool {
+        self.violation(chromosome) == 0.0
+    }
+
+    /// Returns the total distance by which genes stick out of their ranges:
+    /// - 0.0 = all genes are within bounds
+    /// - larger = genes are further away from their ranges
+    pub fn violation<G: Gene>(&self, chromosome: &Chromosome<G>) -> f32 {
+        self.check_len(chromosome);
+
+        chromosome
+            .iter()
+            .enumerate()
+            .map(|(idx, gene)| {
+                let (min, max) = self.range(idx);
+                let gene = gene.to_f32();
+
+                (min - gene).max(0.0) + (gene - max).max(0.0)
+            })
+            .sum()
+    }
+
+    /// Brings all genes back within their ranges, according to the policy.
+    pub fn apply<G: Gene>(&self, chromosome: &mut Chromosome<G>) {
+        self.check_len(chromosome);
+
+        for (idx, gene) in chromosome.iter_mut().enumerate() {
+            let (min, max) = self.range(idx);
+            let value = gene.to_f32();
+
+            // Genes within bounds are left alone, so that they don't get
+            // needlessly converted back and forth
+            if !(min..=max).contains(&value) {
+                let within = G::from_f32(self.bring_within(value, min, max));
+
+                *gene = clamp_rounded(within, min, max);
+            }
+        }
+    }
+
+    fn bring_within(&self, gene: f32, min: f32, max: f32) -> f32 {
+        let range = max - min;
+
+        // Degenerate range allows just a single value
+        if range == 0.0 {
+            return min;
+        }
+
+        match self.policy {
+            BoundaryPolicy::Clamp => gene.clamp(min, max),
+
+            BoundaryPolicy::Reflect => {
+                let offset = (gene - min).rem_euclid(2.0 * range);
+
+                if offset > range {
+                    min + 2.0 * range - offset
+                } else {
+                    min + offset
+                }
+            }
+
+            BoundaryPolicy::Wrap => min + (gene - min).rem_euclid(range),
+        }
+    }
+
+    fn check_len<G>(&self, chromosome: &Chromosome<G>) {
+        if let Ranges::PerGene(ranges) = &self.ranges {
+            assert_eq!(ranges.len(), chromosome.len());
+        }
+    }
+}
+
+/// Pulls back a gene that rounding pushed out of its range (e.g. an integer
+/// gene clamped to 5.6 and rounded up to 6) to the nearest value within it,
+/// or - when there's none - to the one nearest to the range's middle.
+fn clamp_rounded<G: Gene>(gene: G, min: f32, max: f32) -> G {
+    let value = gene.to_f32();
+
+    if (min..=max).contains(&value) {
+        gene
+    } else if min.ceil() > max.floor() {
+        G::from_f32((min + max) / 2.0)
+    } else if value > max {
+        G::from_f32(max.floor())
+    } else {
+        G::from_f32(min.ceil())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn chromosome(genes: &[f32]) -> Chromosome {
+        genes.iter().cloned().collect()
+    }
+
+    fn apply(bounds: GeneBounds, genes: &[f32]) -> Vec<f32> {
+        let mut chromosome = chromosome(genes);
+
+        bounds.apply(&mut chromosome);
+        chromosome.into_iter().collect()
+    }
+
+    const GENES: [f32; 6] = [-2.5, -1.0, 0.25, 1.0, 1.5, 3.75];
+
+    #[test]
+    fn test_clamp() {
+        let actual = apply(GeneBounds::uniform(-1.0, 1.0), &GENES);
+        let expected = vec![-1.0, -1.0, 0.25, 1.0, 1.0, 1.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_reflect() {
+        let bounds =
+            GeneBounds::uniform(-1.0, 1.0).with_policy(BoundaryPolicy::Reflect);
+
+        let actual = apply(bounds, &GENES);
+        let expected = vec![0.5, -1.0, 0.25, 1.0, 0.5, -0.25];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_wrap() {
+        let bounds =
+            GeneBounds::uniform(-1.0, 1.0).with_policy(BoundaryPolicy::Wrap);
+
+        let actual = apply(bounds, &GENES);
+        let expected = vec![-0.5, -1.0, 0.25, 1.0, -0.5, -0.25];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_per_gene_ranges() {
+        let bounds = GeneBounds::new(vec![(0.0, 1.0), (2.0, 2.0), (-5.0, 5.0)]);
+
+        let actual = apply(bounds, &[-1.0, 3.0, 4.0]);
+        let expected = vec![0.0, 2.0, 4.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_violation() {
+        let bounds = GeneBounds::uniform(-1.0, 1.0);
+
+        assert_relative_eq!(bounds.violation(&chromosome(&GENES)), 4.75);
+        assert!(!bounds.contains(&chromosome(&GENES)));
+        assert!(bounds.contains(&chromosome(&[-1.0, 0.0, 1.0])));
+    }
+
+    #[test]
+    fn test_integer_genes() {
+        let policies = [
+            BoundaryPolicy::Clamp,
+            BoundaryPolicy::Reflect,
+            BoundaryPolicy::Wrap,
+        ];
+
+        for &policy in &policies {
+            let bounds = GeneBounds::uniform(0.4, 5.6).with_policy(policy);
+            let mut chromosome: Chromosome<i32> = (-20..20).collect();
+
+            bounds.apply(&mut chromosome);
+
+            assert!(bounds.contains(&chromosome), "{:?}", policy);
+        }
+
+        // Clamping 7 to 5.6 and rounding it would end up at 6
+        let mut chromosome: Chromosome<i32> = vec![-1, 7].into_iter().collect();
+
+        GeneBounds::uniform(0.4, 5.6).apply(&mut chromosome);
+
+        assert_eq!(chromosome.as_slice(), &[1, 5]);
+
+        // There's no integer between 2.2 and 2.4, so the nearest one wins,
+        // no matter where genes started
+        for &policy in &policies {
+            let bounds = GeneBounds::uniform(2.2, 2.4).with_policy(policy);
+            let mut chromosome: Chromosome<i32> = (-5..5).collect();
+
+            bounds.apply(&mut chromosome);
+
+            assert_eq!(chromosome.as_slice(), &[2; 10], "{:?}", policy);
+            assert!(!bounds.contains(&chromosome));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_len() {
+        let bounds = GeneBounds::new(vec![(0.0, 1.0); 2]);
+
+        bounds.apply(&mut chromosome(&[0.5, 0.5, 0.5]));
+    }
+}
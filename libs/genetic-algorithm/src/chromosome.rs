@@ -1,9 +1,13 @@
 #[derive(Clone, Debug, PartialEq)]
-pub struct Chromosome {
-    genes: Vec<f32>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chromosome<G = f32> {
+    genes: Vec<G>,
+    /// Per-gene mutation step sizes used by self-adaptive mutation methods;
+    /// empty when the chromosome doesn't carry any.
+    step_sizes: Vec<f32>,
 }
 
-impl Chromosome {
+impl<G> Chromosome<G> {
     pub fn len(&self) -> usize {
         self.genes.len()
     }
@@ -12,37 +16,85 @@ impl Chromosome {
         self.genes.is_empty()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
         self.genes.iter()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
         self.genes.iter_mut()
     }
+
+    pub fn as_slice(&self) -> &[G] {
+        &self.genes
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [G] {
+        &mut self.genes
+    }
+
+    pub fn set_step_sizes(&mut self, step_sizes: Vec<f32>) {
+        assert_eq!(step_sizes.len(), self.genes.len());
+
+        self.step_sizes = step_sizes;
+    }
+
+    pub fn step_sizes(&self) -> &[f32] {
+        &self.step_sizes
+    }
+
+    pub fn step_sizes_mut(&mut self) -> &mut [f32] {
+        &mut self.step_sizes
+    }
+
+    /// Gives a freshly crossed-over chromosome step sizes averaged from its
+    /// parents', so that they survive crossover methods which only deal
+    /// with genes.
+    pub(crate) fn inherit_step_sizes(
+        &mut self,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) {
+        let len = self.genes.len();
+
+        if !self.step_sizes.is_empty()
+            || parent_a.step_sizes.len() != len
+            || parent_b.step_sizes.len() != len
+        {
+            return;
+        }
+
+        self.step_sizes = parent_a
+            .step_sizes
+            .iter()
+            .zip(&parent_b.step_sizes)
+            .map(|(a, b)| (a + b) / 2.0)
+            .collect();
+    }
 }
 
-impl std::ops::Index<usize> for Chromosome {
-    type Output = f32;
+impl<G> std::ops::Index<usize> for Chromosome<G> {
+    type Output = G;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.genes[index]
     }
 }
 
-impl std::iter::FromIterator<f32> for Chromosome {
+impl<G> std::iter::FromIterator<G> for Chromosome<G> {
     fn from_iter<T>(iter: T) -> Self
     where
-        T: IntoIterator<Item = f32>,
+        T: IntoIterator<Item = G>,
     {
         Self {
             genes: iter.into_iter().collect(),
+            step_sizes: Vec::new(),
         }
     }
 }
 
-impl IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = std::vec::IntoIter<f32>;
+impl<G> IntoIterator for Chromosome<G> {
+    type Item = G;
+    type IntoIter = std::vec::IntoIter<G>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.genes.into_iter()
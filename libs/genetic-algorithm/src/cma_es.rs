@@ -0,0 +1,856 @@
+use crate::*;
+use rand_distr::StandardNormal;
+use std::collections::VecDeque;
+
+/// Restart happens when the fitness range (of the current generation and
+/// of the best individuals of the recent generations) drops below this.
+const TOL_FUN: f32 = 1e-7;
+
+/// Restart happens when all the steps become this much smaller than the
+/// initial step size.
+const TOL_X: f64 = 1e-6;
+
+/// Restart happens when the covariance matrix gets this ill-conditioned.
+const MAX_CONDITION: f64 = 1e14;
+
+/// Covariance matrix adaptation evolution strategy - a state-of-the-art
+/// optimiser for continuous problems with up to a few hundred genes, such
+/// as weights of small neural networks.
+///
+/// Every generation samples individuals from a multivariate normal
+/// distribution, whose mean, covariance matrix and step size are then
+/// adapted towards the fittest samples; chromosomes don't get crossed or
+/// mutated at all.
+///
+/// Like [`DifferentialEvolution`], the returned population always consists
+/// of fresh samples, which get ranked on the *next* call to
+/// [`CmaEs::evolve`] - so fitness can be computed either in
+/// `Individual::from_chromosome` or outside (e.g. by running a simulation).
+///
+/// With [`CmaEs::with_ipop`], whenever the distribution converges, search
+/// restarts from a random point with twice the population size (IPOP), so
+/// the returned population can grow over time.
+#[derive(Clone, Debug)]
+pub struct CmaEs<G = f32, O = NoObserver> {
+    /// Initial step size, i.e. standard deviation of samples around the
+    /// mean; ideally around a quarter of the range where the optimum is
+    /// expected to be.
+    sigma: f32,
+    /// Number of samples per generation:
+    /// - `None` = size of the first population, but at least 2
+    /// - `Some(n)` = `n` samples
+    population_size: Option<usize>,
+    /// Maximum number of restarts, each doubling the population size.
+    max_restarts: usize,
+    /// Optional bounds enforced on every sample.
+    bounds: Option<GeneBounds>,
+    observer: O,
+    state: Option<State>,
+    /// Region (per-gene minimum and maximum) of the first population,
+    /// within which restarts pick their mean.
+    region: Vec<(f64, f64)>,
+    /// Number of restarts so far.
+    restarts: usize,
+    /// Whether the distribution converged with no restarts left.
+    exhausted: bool,
+    /// The fittest sample seen so far, along with its fitness.
+    best: Option<(Chromosome<G>, f32)>,
+    /// Number of generations evolved so far.
+    generation: usize,
+}
+
+impl<G> CmaEs<G> {
+    pub fn new(sigma: f32) -> Self {
+        assert!(sigma > 0.0);
+
+        Self {
+            sigma,
+            population_size: None,
+            max_restarts: 0,
+            bounds: None,
+            observer: NoObserver::new(),
+            state: None,
+            region: Vec::new(),
+            restarts: 0,
+            exhausted: false,
+            best: None,
+            generation: 0,
+        }
+    }
+}
+
+impl<G, O> CmaEs<G, O> {
+    pub fn with_population_size(mut self, population_size: usize) -> Self {
+        assert!(population_size >= 2);
+
+        self.population_size = Some(population_size);
+        self
+    }
+
+    /// Enables IPOP: up to `max_restarts` times, a converged distribution
+    /// gets replaced with a fresh one, centered at a random point of the
+    /// first population's region and sampling twice as many individuals.
+    pub fn with_ipop(mut self, max_restarts: usize) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    pub fn with_bounds(mut self, bounds: GeneBounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Registers observer notified about each generation and each sample;
+    /// parents aren't reported, since samples don't have any.
+    pub fn with_observer<O2>(self, observer: O2) -> CmaEs<G, O2> {
+        CmaEs {
+            sigma: self.sigma,
+            population_size: self.population_size,
+            max_restarts: self.max_restarts,
+            bounds: self.bounds,
+            observer,
+            state: self.state,
+            region: self.region,
+            restarts: self.restarts,
+            exhausted: self.exhausted,
+            best: self.best,
+            generation: self.generation,
+        }
+    }
+
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn restarts(&self) -> usize {
+        self.restarts
+    }
+
+    /// Returns the fittest sample seen so far, along with its fitness.
+    pub fn best(&self) -> Option<(&Chromosome<G>, f32)> {
+        self.best.as_ref().map(|(chromosome, fitness)| (chromosome, *fitness))
+    }
+
+    /// Returns the current step size, if the distribution's been set up
+    /// already.
+    pub fn step_size(&self) -> Option<f32> {
+        self.state.as_ref().map(|state| state.sigma as f32)
+    }
+
+    /// Tells whether the distribution converged and there are no restarts
+    /// left, i.e. whether evolving further is pointless.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
+impl<G, O> CmaEs<G, O>
+where
+    G: Gene,
+    O: EvolutionObserver<G>,
+{
+    /// Ranks samples of the previous generation, adapts the distribution
+    /// to them and returns a new batch of samples.
+    ///
+    /// Returned statistics describe `population`.
+    pub fn evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G>,
+    {
+        assert!(!population.is_empty());
+
+        self.observer.on_generation_start(self.generation);
+
+        match &mut self.state {
+            Some(state) if state.lambda == population.len() => {
+                state.update(population);
+
+                if state.has_converged(population) {
+                    self.restart(rng);
+                }
+            }
+
+            _ => self.initialize(population),
+        }
+
+        self.remember_best(population);
+
+        let Self { state, bounds, observer, .. } = self;
+        let state = state.as_ref().unwrap();
+
+        let samples: Vec<_> = (0..state.lambda)
+            .map(|_| {
+                let mut chromosome: Chromosome<G> = state
+                    .sample(rng)
+                    .into_iter()
+                    .map(|gene| G::from_f32(gene as f32))
+                    .collect();
+
+                if let Some(bounds) = bounds {
+                    bounds.apply(&mut chromosome);
+                }
+
+                let sample = I::from_chromosome(chromosome);
+
+                observer
+                    .on_child_created(sample.chromosome(), sample.fitness());
+
+                sample
+            })
+            .collect();
+
+        let statistics = Statistics::new(population);
+
+        self.observer.on_generation_end(self.generation, &statistics);
+        self.generation += 1;
+
+        (samples, statistics)
+    }
+
+    /// Centers the distribution at the centroid of `population`.
+    fn initialize<I: Individual<G>>(&mut self, population: &[I]) {
+        let dimensions = population[0].chromosome().len();
+        let mut mean = vec![0.0; dimensions];
+
+        self.region = vec![(f64::INFINITY, f64::NEG_INFINITY); dimensions];
+
+        for individual in population {
+            let genes = individual.chromosome().iter().map(Gene::to_f32);
+
+            for ((sum, (min, max)), gene) in
+                mean.iter_mut().zip(&mut self.region).zip(genes)
+            {
+                let gene = gene as f64;
+
+                *sum += gene;
+                *min = min.min(gene);
+                *max = max.max(gene);
+            }
+        }
+
+        for gene in &mut mean {
+            *gene /= population.len() as f64;
+        }
+
+        // Recombination needs at least one parent (mu = lambda / 2)
+        let lambda = self.population_size.unwrap_or(population.len()).max(2);
+
+        self.state = Some(State::new(mean, self.sigma as f64, lambda));
+    }
+
+    /// Starts over from a random point with twice as many samples, unless
+    /// there are no restarts left.
+    fn restart(&mut self, rng: &mut dyn RngCore) {
+        if self.restarts >= self.max_restarts {
+            self.exhausted = true;
+            return;
+        }
+
+        let lambda = 2 * self.state.as_ref().unwrap().lambda;
+
+        let mean = self
+            .region
+            .iter()
+            .map(
+                |&(min, max)| {
+                    if min < max {
+                        rng.gen_range(min..max)
+                    } else {
+                        min
+                    }
+                },
+            )
+            .collect();
+
+        self.restarts += 1;
+        self.state = Some(State::new(mean, self.sigma as f64, lambda));
+    }
+
+    fn remember_best<I: Individual<G>>(&mut self, population: &[I]) {
+        let candidate = population
+            .iter()
+            .max_by(|a, b| a.fitness().total_cmp(&b.fitness()))
+            .unwrap();
+
+        if self
+            .best
+            .as_ref()
+            .map_or(true, |(_, fitness)| candidate.fitness() > *fitness)
+        {
+            self.best =
+                Some((candidate.chromosome().clone(), candidate.fitness()));
+        }
+    }
+}
+
+impl<G, O, I> Evolver<G, I> for CmaEs<G, O>
+where
+    G: Gene,
+    O: EvolutionObserver<G>,
+    I: Individual<G>,
+{
+    fn evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Statistics) {
+        CmaEs::evolve(self, rng, population)
+    }
+
+    fn should_stop(&self) -> bool {
+        self.exhausted || self.observer.should_stop()
+    }
+}
+
+/// Multivariate normal distribution being adapted, along with the
+/// strategy parameters; matrices are stored row by row.
+#[derive(Clone, Debug)]
+struct State {
+    /// Number of samples per generation.
+    lambda: usize,
+    /// Number of the fittest samples the distribution adapts to.
+    mu: usize,
+    /// Recombination weights of the `mu` fittest samples.
+    weights: Vec<f64>,
+    /// Variance effective selection mass.
+    mu_eff: f64,
+    /// Learning rate of the evolution path for the covariance matrix.
+    cc: f64,
+    /// Learning rate of the evolution path for the step size.
+    cs: f64,
+    /// Learning rate of the rank-one update.
+    c1: f64,
+    /// Learning rate of the rank-mu update.
+    cmu: f64,
+    /// Damping of the step size adaptation.
+    damps: f64,
+    /// Expected length of a standard normally distributed vector.
+    chi_n: f64,
+    mean: Vec<f64>,
+    /// Initial step size, for detecting convergence.
+    initial_sigma: f64,
+    sigma: f64,
+    covariance: Vec<f64>,
+    /// Eigenvectors of the covariance matrix, as columns.
+    basis: Vec<f64>,
+    /// Square roots of eigenvalues of the covariance matrix.
+    scales: Vec<f64>,
+    /// Evolution path for the covariance matrix.
+    pc: Vec<f64>,
+    /// Evolution path for the step size.
+    ps: Vec<f64>,
+    /// Number of updates since the distribution got created.
+    generation: usize,
+    /// Generation in which the covariance matrix got decomposed last.
+    decomposed_at: usize,
+    /// Best fitness of the recent generations, the newest one last.
+    history: VecDeque<f32>,
+}
+
+impl State {
+    fn new(mean: Vec<f64>, sigma: f64, lambda: usize) -> Self {
+        let n = mean.len() as f64;
+        let mu = lambda / 2;
+
+        let weights: Vec<_> = (1..=mu)
+            .map(|rank| (mu as f64 + 0.5).ln() - (rank as f64).ln())
+            .collect();
+
+        let sum: f64 = weights.iter().sum();
+        let weights: Vec<_> = weights.iter().map(|w| w / sum).collect();
+        let mu_eff = 1.0 / weights.iter().map(|w| w * w).sum::<f64>();
+
+        let cc = (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n);
+        let cs = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let c1 = 2.0 / ((n + 1.3).powi(2) + mu_eff);
+
+        let cmu = (1.0 - c1).min(
+            2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0).powi(2) + mu_eff),
+        );
+
+        let damps = 1.0
+            + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0)
+            + cs;
+
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        let dimensions = mean.len();
+
+        Self {
+            lambda,
+            mu,
+            weights,
+            mu_eff,
+            cc,
+            cs,
+            c1,
+            cmu,
+            damps,
+            chi_n,
+            mean,
+            initial_sigma: sigma,
+            sigma,
+            covariance: identity(dimensions),
+            basis: identity(dimensions),
+            scales: vec![1.0; dimensions],
+            pc: vec![0.0; dimensions],
+            ps: vec![0.0; dimensions],
+            generation: 0,
+            decomposed_at: 0,
+            history: VecDeque::new(),
+        }
+    }
+
+    fn dimensions(&self) -> usize {
+        self.mean.len()
+    }
+
+    /// Returns `mean + sigma * B * D * z`, with `z` being a standard
+    /// normally distributed vector.
+    fn sample(&self, rng: &mut dyn RngCore) -> Vec<f64> {
+        let n = self.dimensions();
+
+        let scaled: Vec<_> = self
+            .scales
+            .iter()
+            .map(|scale| scale * rng.sample::<f64, _>(StandardNormal))
+            .collect();
+
+        (0..n)
+            .map(|row| {
+                let step: f64 = (0..n)
+                    .map(|col| self.basis[row * n + col] * scaled[col])
+                    .sum();
+
+                self.mean[row] + self.sigma * step
+            })
+            .collect()
+    }
+
+    /// Adapts the distribution to the fittest individuals of `population`.
+    fn update<G, I>(&mut self, population: &[I])
+    where
+        G: Gene,
+        I: Individual<G>,
+    {
+        let n = self.dimensions();
+
+        let mut ranked: Vec<_> = population.iter().collect();
+        ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        // Steps of the fittest individuals, recomputed from their genes, so
+        // that bounds (or any other repair) are accounted for
+        let steps: Vec<Vec<_>> = ranked[..self.mu]
+            .iter()
+            .map(|individual| {
+                individual
+                    .chromosome()
+                    .iter()
+                    .zip(&self.mean)
+                    .map(|(gene, mean)| {
+                        (gene.to_f32() as f64 - mean) / self.sigma
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let step: Vec<_> = (0..n)
+            .map(|i| {
+                self.weights.iter().zip(&steps).map(|(w, y)| w * y[i]).sum()
+            })
+            .collect();
+
+        for (mean, step) in self.mean.iter_mut().zip(&step) {
+            *mean += self.sigma * step;
+        }
+
+        // Step size path uses `C^(-1/2) * step = B * D^(-1) * B^T * step`
+        let rotated: Vec<_> = (0..n)
+            .map(|col| {
+                (0..n)
+                    .map(|row| self.basis[row * n + col] * step[row])
+                    .sum::<f64>()
+                    / self.scales[col]
+            })
+            .collect();
+
+        let cs = (self.cs * (2.0 - self.cs) * self.mu_eff).sqrt();
+
+        let whitened: Vec<f64> = (0..n)
+            .map(|row| {
+                (0..n).map(|col| self.basis[row * n + col] * rotated[col]).sum()
+            })
+            .collect();
+
+        for (ps, whitened) in self.ps.iter_mut().zip(&whitened) {
+            *ps = (1.0 - self.cs) * *ps + cs * whitened;
+        }
+
+        self.generation += 1;
+
+        let ps_norm = norm(&self.ps);
+
+        // Stalls the covariance path while the step size grows quickly
+        let hsig = ps_norm
+            / (1.0 - (1.0 - self.cs).powi(2 * self.generation as i32)).sqrt()
+            / self.chi_n
+            < 1.4 + 2.0 / (n as f64 + 1.0);
+
+        let hsig = if hsig { 1.0 } else { 0.0 };
+        let cc = (self.cc * (2.0 - self.cc) * self.mu_eff).sqrt();
+
+        for (pc, step) in self.pc.iter_mut().zip(&step) {
+            *pc = (1.0 - self.cc) * *pc + hsig * cc * step;
+        }
+
+        let correction = (1.0 - hsig) * self.cc * (2.0 - self.cc);
+
+        for row in 0..n {
+            for col in row..n {
+                let rank_one = self.pc[row] * self.pc[col]
+                    + correction * self.covariance[row * n + col];
+
+                let rank_mu: f64 = self
+                    .weights
+                    .iter()
+                    .zip(&steps)
+                    .map(|(w, y)| w * y[row] * y[col])
+                    .sum();
+
+                let value = (1.0 - self.c1 - self.cmu)
+                    * self.covariance[row * n + col]
+                    + self.c1 * rank_one
+                    + self.cmu * rank_mu;
+
+                self.covariance[row * n + col] = value;
+                self.covariance[col * n + row] = value;
+            }
+        }
+
+        self.sigma *=
+            ((self.cs / self.damps) * (ps_norm / self.chi_n - 1.0)).exp();
+
+        // Decomposing is O(n^3), so - since the covariance matrix changes
+        // slowly anyway - it's done only every few generations
+        let interval = 1.0 / ((self.c1 + self.cmu) * n as f64 * 10.0);
+
+        if (self.generation - self.decomposed_at) as f64 >= interval {
+            let (eigenvalues, eigenvectors) =
+                eigen_decomposition(&self.covariance, n);
+
+            self.scales = eigenvalues
+                .iter()
+                .map(|value| value.max(1e-20).sqrt())
+                .collect();
+
+            self.basis = eigenvectors;
+            self.decomposed_at = self.generation;
+        }
+    }
+
+    /// Tells whether the distribution stopped making progress, judging by
+    /// its state after an update with `population`.
+    fn has_converged<G, I>(&mut self, population: &[I]) -> bool
+    where
+        G: Gene,
+        I: Individual<G>,
+    {
+        let n = self.dimensions();
+        let fitnesses = population.iter().map(Individual::fitness);
+
+        let (min, max) = fitnesses
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), fitness| {
+                (min.min(fitness), max.max(fitness))
+            });
+
+        let history_len =
+            10 + (30.0 * n as f64 / self.lambda as f64).ceil() as usize;
+
+        self.history.push_back(max);
+
+        if self.history.len() > history_len {
+            self.history.pop_front();
+        }
+
+        let (history_min, history_max) = self.history.iter().fold(
+            (f32::INFINITY, f32::NEG_INFINITY),
+            |(min, max), &fitness| (min.min(fitness), max.max(fitness)),
+        );
+
+        let no_progress = self.history.len() == history_len
+            && history_max - history_min < TOL_FUN
+            && max - min < TOL_FUN;
+
+        let max_deviation = (0..n)
+            .map(|i| self.covariance[i * n + i].sqrt())
+            .fold(0.0, f64::max);
+
+        let tiny_steps =
+            self.sigma * max_deviation < TOL_X * self.initial_sigma;
+
+        let max_scale = self.scales.iter().cloned().fold(0.0, f64::max);
+        let min_scale =
+            self.scales.iter().cloned().fold(f64::INFINITY, f64::min);
+        let ill_conditioned = (max_scale / min_scale).powi(2) > MAX_CONDITION;
+
+        !self.sigma.is_finite() || no_progress || tiny_steps || ill_conditioned
+    }
+}
+
+fn identity(n: usize) -> Vec<f64> {
+    let mut matrix = vec![0.0; n * n];
+
+    for i in 0..n {
+        matrix[i * n + i] = 1.0;
+    }
+
+    matrix
+}
+
+fn norm(vector: &[f64]) -> f64 {
+    vector.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+/// Decomposes a symmetric `n x n` matrix using the cyclic Jacobi method;
+/// returns eigenvalues and eigenvectors (as columns of a matrix).
+fn eigen_decomposition(matrix: &[f64], n: usize) -> (Vec<f64>, Vec<f64>) {
+    let mut a = matrix.to_vec();
+    let mut v = identity(n);
+
+    let total: f64 = a.iter().map(|x| x * x).sum();
+
+    for _ in 0..100 {
+        let off_diagonal: f64 = (0..n)
+            .flat_map(|p| (p + 1..n).map(move |q| (p, q)))
+            .map(|(p, q)| a[p * n + q] * a[p * n + q])
+            .sum();
+
+        if off_diagonal <= 1e-24 * total {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[p * n + q];
+
+                if apq == 0.0 {
+                    continue;
+                }
+
+                // Rotation zeroing `a[p][q]`
+                let theta = (a[q * n + q] - a[p * n + p]) / (2.0 * apq);
+                let t = theta.signum()
+                    / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for k in 0..n {
+                    let (akp, akq) = (a[k * n + p], a[k * n + q]);
+
+                    a[k * n + p] = c * akp - s * akq;
+                    a[k * n + q] = s * akp + c * akq;
+                }
+
+                for k in 0..n {
+                    let (apk, aqk) = (a[p * n + k], a[q * n + k]);
+
+                    a[p * n + k] = c * apk - s * aqk;
+                    a[q * n + k] = s * apk + c * aqk;
+                }
+
+                for k in 0..n {
+                    let (vkp, vkq) = (v[k * n + p], v[k * n + q]);
+
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    ((0..n).map(|i| a[i * n + i]).collect(), v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Ill-conditioned ellipsoid: fitness is the negated, weighted squared
+    /// distance from the origin, with weights ranging from 1 to 10^4.
+    #[derive(Clone, Debug)]
+    struct Ellipsoid {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for Ellipsoid {
+        fn from_chromosome(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            let n = self.chromosome.len() as f32;
+
+            -self
+                .chromosome
+                .iter()
+                .enumerate()
+                .map(|(i, gene)| {
+                    10f32.powf(4.0 * i as f32 / (n - 1.0)) * gene * gene
+                })
+                .sum::<f32>()
+        }
+    }
+
+    /// Individual every one of which is as good as any other.
+    struct Plateau {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for Plateau {
+        fn from_chromosome(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            1.0
+        }
+    }
+
+    fn population<I: Individual>(rng: &mut ChaCha8Rng, size: usize) -> Vec<I> {
+        (0..size)
+            .map(|_| {
+                let genes = (0..4).map(|_| rng.gen_range(-5.0..5.0));
+
+                I::from_chromosome(genes.collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ellipsoid() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut cma_es = CmaEs::new(2.0);
+        let mut population: Vec<Ellipsoid> = population(&mut rng, 8);
+
+        for _ in 0..200 {
+            population = cma_es.evolve(&mut rng, &population).0;
+        }
+
+        assert!(cma_es.best().unwrap().1 > -1e-6);
+        assert!(cma_es.step_size().unwrap() < 0.01);
+    }
+
+    #[test]
+    fn test_population_size() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut cma_es = CmaEs::new(1.0).with_population_size(12);
+        let population: Vec<Ellipsoid> = population(&mut rng, 5);
+
+        let population = cma_es.evolve(&mut rng, &population).0;
+
+        assert_eq!(population.len(), 12);
+        assert_eq!(cma_es.evolve(&mut rng, &population).0.len(), 12);
+    }
+
+    #[test]
+    fn test_single_individual() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut cma_es = CmaEs::new(1.0);
+        let mut population: Vec<Ellipsoid> = population(&mut rng, 1);
+
+        for _ in 0..10 {
+            population = cma_es.evolve(&mut rng, &population).0;
+
+            assert_eq!(population.len(), 2);
+        }
+
+        assert!(cma_es.step_size().unwrap().is_finite());
+        assert!(cma_es.best().unwrap().1.is_finite());
+    }
+
+    #[test]
+    fn test_ipop() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut cma_es = CmaEs::new(1.0).with_ipop(2);
+        let mut population: Vec<Plateau> = population(&mut rng, 10);
+        let mut sizes = vec![population.len()];
+
+        while !Evolver::<f32, Plateau>::should_stop(&cma_es) {
+            population = cma_es.evolve(&mut rng, &population).0;
+
+            if sizes.last() != Some(&population.len()) {
+                sizes.push(population.len());
+            }
+        }
+
+        assert_eq!(sizes, vec![10, 20, 40]);
+        assert_eq!(cma_es.restarts(), 2);
+        assert!(cma_es.is_exhausted());
+    }
+
+    #[test]
+    fn test_with_bounds() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let bounds = GeneBounds::uniform(1.0, 2.0);
+        let mut cma_es = CmaEs::new(3.0).with_bounds(bounds.clone());
+        let mut population: Vec<Ellipsoid> = population(&mut rng, 8);
+
+        for _ in 0..50 {
+            population = cma_es.evolve(&mut rng, &population).0;
+
+            assert!(population
+                .iter()
+                .all(|individual| bounds.contains(individual.chromosome())));
+        }
+
+        // The best point within bounds is the lower corner, where fitness
+        // is ~-10486.6
+        assert!(cma_es.best().unwrap().1 > -10487.0);
+    }
+
+    #[test]
+    fn test_eigen_decomposition() {
+        let matrix = vec![4.0, 1.0, 2.0, 1.0, 3.0, 0.0, 2.0, 0.0, 5.0];
+        let (values, vectors) = eigen_decomposition(&matrix, 3);
+
+        // A * v = lambda * v, for every eigenpair
+        for (i, value) in values.iter().enumerate() {
+            for row in 0..3 {
+                let actual: f64 = (0..3)
+                    .map(|col| matrix[row * 3 + col] * vectors[col * 3 + i])
+                    .sum();
+
+                assert_relative_eq!(
+                    actual,
+                    value * vectors[row * 3 + i],
+                    epsilon = 1e-9
+                );
+            }
+        }
+
+        assert_relative_eq!(values.iter().sum::<f64>(), 12.0, epsilon = 1e-9);
+    }
+}
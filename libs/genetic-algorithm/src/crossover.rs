@@ -1,15 +1,18 @@
 use crate::*;
+use rand::seq::index;
+use std::ops::Range;
 
-pub trait CrossoverMethod {
+pub trait CrossoverMethod<G = f32> {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome;
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G>;
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UniformCrossover;
 
 impl UniformCrossover {
@@ -18,7 +21,159 @@ impl UniformCrossover {
     }
 }
 
-impl CrossoverMethod for UniformCrossover {
+impl<G: Clone> CrossoverMethod<G> for UniformCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(a, b)| if rng.gen_bool(0.5) { a } else { b })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Takes genes before a random cut point from `parent_a` and the rest from
+/// `parent_b`.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SinglePointCrossover;
+
+impl SinglePointCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G: Clone> CrossoverMethod<G> for SinglePointCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        KPointCrossover::new(1).crossover(rng, parent_a, parent_b)
+    }
+}
+
+/// Cuts parents at `k` random points and takes segments alternately from
+/// `parent_a` and `parent_b`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KPointCrossover {
+    k: usize,
+}
+
+impl KPointCrossover {
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0);
+
+        Self { k }
+    }
+}
+
+impl<G: Clone> CrossoverMethod<G> for KPointCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        // Cut points lie strictly between genes, i.e. in `1..len`
+        let slots = parent_a.len().saturating_sub(1);
+        let mut points =
+            index::sample(rng, slots, self.k.min(slots)).into_vec();
+
+        points.sort_unstable();
+
+        let mut points = points.into_iter().map(|point| point + 1).peekable();
+        let mut from_a = true;
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .enumerate()
+            .map(|(idx, (a, b))| {
+                if points.peek() == Some(&idx) {
+                    points.next();
+                    from_a = !from_a;
+                }
+
+                if from_a {
+                    a
+                } else {
+                    b
+                }
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Creates a child whose genes are weighted averages of parents' genes.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArithmeticCrossover {
+    /// Weight of `parent_a`'s genes:
+    /// - 0.0 = child is a copy of `parent_b`
+    /// - 0.5 = child is the exact average of both parents
+    /// - 1.0 = child is a copy of `parent_a`
+    alpha: f32,
+}
+
+impl ArithmeticCrossover {
+    pub fn new(alpha: f32) -> Self {
+        assert!((0.0..=1.0).contains(&alpha));
+
+        Self { alpha }
+    }
+}
+
+impl CrossoverMethod for ArithmeticCrossover {
+    fn crossover(
+        &self,
+        _rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| self.alpha * a + (1.0 - self.alpha) * b)
+            .collect()
+    }
+}
+
+/// BLX-α: draws every gene uniformly from the range spanned by parents'
+/// genes, extended on both sides by `alpha` times its length.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlendCrossover {
+    /// Extension of the range:
+    /// - 0.0 = genes are drawn strictly between parents' genes
+    /// - 0.5 = genes are drawn from a range twice as wide
+    alpha: f32,
+}
+
+impl BlendCrossover {
+    pub fn new(alpha: f32) -> Self {
+        assert!(alpha >= 0.0);
+
+        Self { alpha }
+    }
+}
+
+impl CrossoverMethod for BlendCrossover {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
@@ -30,14 +185,314 @@ impl CrossoverMethod for UniformCrossover {
         parent_a
             .iter()
             .zip(parent_b.iter())
-            .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
+            .map(|(&a, &b)| {
+                let extension = self.alpha * (a - b).abs();
+                let min = a.min(b) - extension;
+                let max = a.max(b) + extension;
+
+                min + (max - min) * rng.gen::<f32>()
+            })
             .collect()
     }
 }
 
+/// Uniform crossover over whole neurons of a layered neural network.
+///
+/// Genes are expected in the order `neural_network::Network::weights`
+/// yields them: for every neuron of every non-input layer, its bias followed
+/// by its incoming weights. Each neuron is inherited as a unit from one of
+/// the parents, so functional building blocks don't get torn apart.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NeuronCrossover {
+    /// Number of genes describing each neuron, in chromosome order.
+    neurons: Vec<usize>,
+}
+
+impl NeuronCrossover {
+    /// Creates crossover for a network with given numbers of neurons per
+    /// layer, starting with the input layer (i.e. `LayerTopology::neurons`).
+    pub fn new(layers: &[usize]) -> Self {
+        assert!(layers.len() > 1);
+
+        let neurons = layers
+            .windows(2)
+            .flat_map(|layers| {
+                std::iter::repeat(layers[0] + 1).take(layers[1])
+            })
+            .collect();
+
+        Self { neurons }
+    }
+}
+
+impl<G: Clone> CrossoverMethod<G> for NeuronCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+        assert_eq!(parent_a.len(), self.neurons.iter().sum::<usize>());
+
+        let mut child = Vec::with_capacity(parent_a.len());
+
+        for &genes in &self.neurons {
+            let parent = if rng.gen_bool(0.5) { parent_a } else { parent_b };
+            let offset = child.len();
+
+            child.extend(
+                (offset..offset + genes).map(|idx| parent[idx].clone()),
+            );
+        }
+
+        child.into_iter().collect()
+    }
+}
+
+/// Simulated binary crossover (SBX) for real-coded genes.
+///
+/// Spreads children around parents the way single-point crossover does for
+/// binary-coded genes: children stay near their parents, the closer the
+/// larger the distribution index is.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulatedBinaryCrossover {
+    /// Distribution index:
+    /// - 0.0 = children can land far away from parents
+    /// - 20.0 = children land almost on top of parents
+    eta: f32,
+}
+
+impl SimulatedBinaryCrossover {
+    pub fn new(eta: f32) -> Self {
+        assert!(eta >= 0.0);
+
+        Self { eta }
+    }
+}
+
+impl CrossoverMethod for SimulatedBinaryCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let u = rng.gen::<f32>();
+
+                let beta = if u <= 0.5 {
+                    (2.0 * u).powf(exponent)
+                } else {
+                    (1.0 / (2.0 * (1.0 - u))).powf(exponent)
+                };
+
+                // SBX yields two children symmetric around parents' mean;
+                // we only need one, so we pick either of them
+                let spread = if rng.gen_bool(0.5) { beta } else { -beta };
+
+                0.5 * ((1.0 + spread) * a + (1.0 - spread) * b)
+            })
+            .collect()
+    }
+}
+
+/// Order crossover (OX) for permutations.
+///
+/// Copies a random segment of `parent_a` and fills the remaining positions
+/// with the missing genes in the order they appear in `parent_b`, starting
+/// right after the segment. Parents must be permutations of each other.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderCrossover;
+
+impl OrderCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G: Clone + PartialEq> CrossoverMethod<G> for OrderCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let len = parent_a.len();
+        let segment = random_segment(rng, len);
+        let kept: Vec<_> = segment.clone().map(|idx| &parent_a[idx]).collect();
+
+        let mut child = vec![None; len];
+
+        for idx in segment.clone() {
+            child[idx] = Some(parent_a[idx].clone());
+        }
+
+        let mut missing = (0..len)
+            .map(|offset| &parent_b[(segment.end + offset) % len])
+            .filter(|gene| !kept.contains(gene));
+
+        for offset in 0..len {
+            let idx = (segment.end + offset) % len;
+
+            if child[idx].is_none() {
+                child[idx] = missing.next().cloned();
+            }
+        }
+
+        into_chromosome(child)
+    }
+}
+
+/// Partially mapped crossover (PMX) for permutations.
+///
+/// Copies a random segment of `parent_a` and takes the remaining genes from
+/// `parent_b` at their original positions; genes that would clash with the
+/// segment are relocated through the mapping the segment defines between
+/// both parents. Parents must be permutations of each other.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartiallyMappedCrossover;
+
+impl PartiallyMappedCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G: Clone + PartialEq> CrossoverMethod<G> for PartiallyMappedCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let len = parent_a.len();
+        let segment = random_segment(rng, len);
+
+        let mut child = vec![None; len];
+
+        for idx in segment.clone() {
+            child[idx] = Some(parent_a[idx].clone());
+        }
+
+        for idx in segment.clone() {
+            let gene = &parent_b[idx];
+
+            if segment.clone().any(|idx| parent_a[idx] == *gene) {
+                continue;
+            }
+
+            // Follow the mapping until we land outside of the segment
+            let mut target = idx;
+
+            while segment.contains(&target) {
+                target = position(parent_b, &parent_a[target]);
+            }
+
+            child[target] = Some(gene.clone());
+        }
+
+        for (idx, gene) in child.iter_mut().enumerate() {
+            if gene.is_none() {
+                *gene = Some(parent_b[idx].clone());
+            }
+        }
+
+        into_chromosome(child)
+    }
+}
+
+/// Cycle crossover (CX) for permutations.
+///
+/// Splits positions into cycles and takes cycles alternately from
+/// `parent_a` and `parent_b`, so that every gene stays at a position it has
+/// in one of the parents. Parents must be permutations of each other.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CycleCrossover;
+
+impl CycleCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G: Clone + PartialEq> CrossoverMethod<G> for CycleCrossover {
+    fn crossover(
+        &self,
+        _rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let mut child = vec![None; parent_a.len()];
+        let mut from_a = true;
+
+        for start in 0..child.len() {
+            if child[start].is_some() {
+                continue;
+            }
+
+            let parent = if from_a { parent_a } else { parent_b };
+            let mut idx = start;
+
+            while child[idx].is_none() {
+                child[idx] = Some(parent[idx].clone());
+                idx = position(parent_a, &parent_b[idx]);
+            }
+
+            from_a = !from_a;
+        }
+
+        into_chromosome(child)
+    }
+}
+
+/// Returns a random, possibly empty, range of positions.
+pub(crate) fn random_segment(
+    rng: &mut dyn RngCore,
+    len: usize,
+) -> Range<usize> {
+    let a = rng.gen_range(0..=len);
+    let b = rng.gen_range(0..=len);
+
+    a.min(b)..a.max(b)
+}
+
+fn position<G: PartialEq>(chromosome: &Chromosome<G>, gene: &G) -> usize {
+    chromosome
+        .iter()
+        .position(|other| other == gene)
+        .expect("parents aren't permutations of each other")
+}
+
+fn into_chromosome<G>(genes: Vec<Option<G>>) -> Chromosome<G> {
+    genes
+        .into_iter()
+        .map(|gene| gene.expect("parents aren't permutations of each other"))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use approx::assert_relative_eq;
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
 
@@ -62,4 +517,294 @@ mod tests {
         assert_eq!(diff_a, 49);
         assert_eq!(diff_b, 51);
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_single_point_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+        let child = SinglePointCrossover::new()
+            .crossover(&mut rng, &parent_a, &parent_b);
+
+        // Number of genes different between `child` and `parent_a`
+        let diff_a =
+            child.iter().zip(parent_a.iter()).filter(|(c, p)| c != p).count();
+
+        // Number of genes different between `child` and `parent_b`
+        let diff_b =
+            child.iter().zip(parent_b.iter()).filter(|(c, p)| c != p).count();
+
+        assert_eq!(diff_a, 81);
+        assert_eq!(diff_b, 19);
+        assert_eq!(switches(&child), 1);
+        assert_eq!(child[0], parent_a[0]);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_k_point_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+        let child =
+            KPointCrossover::new(5).crossover(&mut rng, &parent_a, &parent_b);
+
+        // Number of genes different between `child` and `parent_a`
+        let diff_a =
+            child.iter().zip(parent_a.iter()).filter(|(c, p)| c != p).count();
+
+        // Number of genes different between `child` and `parent_b`
+        let diff_b =
+            child.iter().zip(parent_b.iter()).filter(|(c, p)| c != p).count();
+
+        assert_eq!(diff_a, 55);
+        assert_eq!(diff_b, 45);
+        assert_eq!(switches(&child), 5);
+        assert_eq!(child[0], parent_a[0]);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_k_point_crossover_with_too_many_points() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = (1..=4).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=4).map(|n| -n as f32).collect();
+        let child =
+            KPointCrossover::new(10).crossover(&mut rng, &parent_a, &parent_b);
+
+        let expected: Chromosome =
+            vec![1.0, -2.0, 3.0, -4.0].into_iter().collect();
+        assert_eq!(child, expected);
+    }
+
+    #[test]
+    fn test_arithmetic_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = vec![1.0, 2.0, -4.0].into_iter().collect();
+        let parent_b: Chromosome = vec![3.0, -2.0, 0.0].into_iter().collect();
+        let child = ArithmeticCrossover::new(0.25)
+            .crossover(&mut rng, &parent_a, &parent_b);
+
+        let actual: Vec<_> = child.into_iter().collect();
+        let expected = vec![2.5, -1.0, -1.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_blend_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+        let child =
+            BlendCrossover::new(0.5).crossover(&mut rng, &parent_a, &parent_b);
+
+        // Every gene lies within the range extended by a half of its length
+        let within_range = child
+            .iter()
+            .zip(parent_a.iter())
+            .all(|(&c, &a)| c >= -2.0 * a && c <= 2.0 * a);
+
+        // Number of genes outside of the range spanned by parents
+        let outside = child
+            .iter()
+            .zip(parent_a.iter())
+            .filter(|(&c, &a)| c < -a || c > a)
+            .count();
+
+        assert!(within_range);
+        assert_eq!(outside, 40);
+    }
+
+    #[test]
+    fn test_neuron_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        // 3 inputs, 4 hidden neurons (4 genes each), 2 outputs (5 genes each)
+        let method = NeuronCrossover::new(&[3, 4, 2]);
+
+        let parent_a: Chromosome = (1..=26).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=26).map(|n| -n as f32).collect();
+        let child = method.crossover(&mut rng, &parent_a, &parent_b);
+
+        // Every neuron is inherited as a whole from one of the parents
+        let mut genes = child.iter();
+
+        let neurons: Vec<_> = [4, 4, 4, 4, 5, 5]
+            .iter()
+            .map(|&len| {
+                let neuron: Vec<_> = genes.by_ref().take(len).collect();
+                let positive = neuron.iter().filter(|&&&g| g > 0.0).count();
+
+                assert!(positive == 0 || positive == len);
+                positive > 0
+            })
+            .collect();
+
+        assert_eq!(neurons, vec![false, false, true, true, true, true]);
+
+        // Genes keep their positions
+        let positions_kept =
+            child.iter().zip(parent_a.iter()).all(|(c, a)| c.abs() == *a);
+
+        assert!(positions_kept);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_neuron_crossover_with_wrong_topology() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = (1..=10).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=10).map(|n| -n as f32).collect();
+
+        NeuronCrossover::new(&[3, 2]).crossover(&mut rng, &parent_a, &parent_b);
+    }
+
+    #[test]
+    fn test_simulated_binary_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+        let child = SimulatedBinaryCrossover::new(2.0)
+            .crossover(&mut rng, &parent_a, &parent_b);
+
+        // Number of genes closer to `parent_a` than to `parent_b`
+        let closer_to_a = child.iter().filter(|&&c| c > 0.0).count();
+
+        // Number of genes outside of the range spanned by parents
+        let outside = child
+            .iter()
+            .zip(parent_a.iter())
+            .filter(|(&c, &a)| c < -a || c > a)
+            .count();
+
+        assert_eq!(closer_to_a, 50);
+        assert_eq!(outside, 53);
+    }
+
+    #[test]
+    fn test_simulated_binary_crossover_distribution_index() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome = vec![1.0; 1000].into_iter().collect();
+        let parent_b: Chromosome = vec![-1.0; 1000].into_iter().collect();
+
+        // Average distance between child's genes and the nearest parent
+        let mut distance = |eta| {
+            let child = SimulatedBinaryCrossover::new(eta)
+                .crossover(&mut rng, &parent_a, &parent_b);
+
+            child.iter().map(|c| 1.0 - c.abs()).map(f32::abs).sum::<f32>()
+                / 1000.0
+        };
+
+        let distance_low = distance(1.0);
+        let distance_high = distance(20.0);
+
+        assert_relative_eq!(distance_low, 0.57506555);
+        assert_relative_eq!(distance_high, 0.04678262);
+    }
+
+    #[test]
+    fn test_order_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a = permutation(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let parent_b = permutation(&[9, 3, 7, 8, 2, 6, 5, 1, 4, 0]);
+        let child =
+            OrderCrossover::new().crossover(&mut rng, &parent_a, &parent_b);
+
+        assert_permutation(&child);
+        assert_eq!(genes(child), vec![9, 1, 2, 3, 4, 5, 6, 7, 8, 0]);
+    }
+
+    #[test]
+    fn test_partially_mapped_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a = permutation(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let parent_b = permutation(&[3, 9, 7, 8, 2, 6, 5, 1, 0, 4]);
+        let child = PartiallyMappedCrossover::new()
+            .crossover(&mut rng, &parent_a, &parent_b);
+
+        // Segment `2..9` comes from `parent_a`, so `parent_b`'s 1 and 0 get
+        // relocated through the mapping (7 -> 2 -> 4 and 8 -> 3) to the
+        // positions of 4 and 3
+        assert_permutation(&child);
+        assert_eq!(genes(child), vec![0, 9, 2, 3, 4, 5, 6, 7, 8, 1]);
+    }
+
+    #[test]
+    fn test_cycle_crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a = permutation(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let parent_b = permutation(&[8, 5, 2, 1, 3, 6, 4, 7]);
+        let child =
+            CycleCrossover::new().crossover(&mut rng, &parent_a, &parent_b);
+
+        // Cycles: {1, 8, 7, 4} from `parent_a`, {2, 5, 3} from `parent_b`
+        // and {6} from `parent_a` again
+        assert_eq!(genes(child), vec![1, 5, 2, 4, 3, 6, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_permutation_crossover_of_different_genes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a = permutation(&[1, 2, 3]);
+        let parent_b = permutation(&[4, 5, 6]);
+
+        CycleCrossover::new().crossover(&mut rng, &parent_a, &parent_b);
+    }
+
+    #[test]
+    fn test_crossover_of_binary_chromosomes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let parent_a: Chromosome<bool> = vec![true; 100].into_iter().collect();
+        let parent_b: Chromosome<bool> = vec![false; 100].into_iter().collect();
+        let child =
+            KPointCrossover::new(3).crossover(&mut rng, &parent_a, &parent_b);
+
+        let switches =
+            genes(child).windows(2).filter(|pair| pair[0] != pair[1]).count();
+
+        assert_eq!(switches, 3);
+    }
+
+    fn permutation(genes: &[usize]) -> Chromosome<usize> {
+        genes.iter().cloned().collect()
+    }
+
+    fn genes<G>(chromosome: Chromosome<G>) -> Vec<G> {
+        chromosome.into_iter().collect()
+    }
+
+    fn assert_permutation(chromosome: &Chromosome<usize>) {
+        let mut genes: Vec<_> = chromosome.iter().cloned().collect();
+
+        genes.sort_unstable();
+
+        assert_eq!(genes, (0..chromosome.len()).collect::<Vec<_>>());
+    }
+
+    /// Counts how many times genes' sign changes along the chromosome.
+    fn switches(chromosome: &Chromosome) -> usize {
+        let genes: Vec<_> = chromosome.iter().collect();
+
+        genes
+            .windows(2)
+            .filter(|pair| pair[0].signum() != pair[1].signum())
+            .count()
+    }
 }
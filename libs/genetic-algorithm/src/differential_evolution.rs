@@ -0,0 +1,526 @@
+use crate::*;
+use rand::seq::index;
+
+/// How [`DifferentialEvolution`] builds mutant vectors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DifferentialVariant {
+    /// `v = x_r1 + F * (x_r2 - x_r3)`; explores the most.
+    #[default]
+    Rand1Bin,
+    /// `v = x_best + F * (x_r1 - x_r2)`; converges the fastest.
+    Best1Bin,
+    /// `v = x_i + F * (x_best - x_i) + F * (x_r1 - x_r2)`; a compromise
+    /// between the two.
+    CurrentToBest1,
+}
+
+/// Differential evolution - an optimiser for continuous problems, often
+/// outperforming [`GeneticAlgorithm`] on them.
+///
+/// Every individual (the target) gets a trial counterpart built from
+/// differences between other individuals; the trial replaces its target
+/// only if it turns out at least as fit.
+///
+/// Trials get compared with their targets on the *next* call to
+/// [`DifferentialEvolution::evolve`], once they've been evaluated - so
+/// fitness can be computed either in `Individual::from_chromosome` or
+/// outside (e.g. by running a simulation), and the returned population
+/// always consists of trials waiting for evaluation.
+#[derive(Clone, Debug)]
+pub struct DifferentialEvolution<G = f32, O = NoObserver> {
+    variant: DifferentialVariant,
+    /// Differential weight (F), scaling differences between individuals:
+    /// - close to 0.0 = small, cautious steps
+    /// - 1.0 and more = big, exploratory steps
+    weight: f32,
+    /// Crossover rate (CR), i.e. probability of taking a gene from the
+    /// mutant vector instead of the target:
+    /// - 0.0 = only one gene changes at a time (good for separable problems)
+    /// - 1.0 = all genes come from the mutant vector
+    crossover_rate: f32,
+    /// Whether every individual evolves its own F and CR (jDE).
+    self_adaptive: bool,
+    /// Optional bounds enforced on every trial.
+    bounds: Option<GeneBounds>,
+    observer: O,
+    /// Current targets, along with their fitness and parameters.
+    members: Vec<Member<G>>,
+    /// Parameters trials got built with, in population order.
+    trials: Vec<Parameters>,
+    /// Number of generations evolved so far.
+    generation: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Member<G> {
+    chromosome: Chromosome<G>,
+    fitness: f32,
+    parameters: Parameters,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Parameters {
+    weight: f32,
+    crossover_rate: f32,
+}
+
+impl<G> DifferentialEvolution<G> {
+    pub fn new(weight: f32, crossover_rate: f32) -> Self {
+        assert!(weight > 0.0);
+        assert!((0.0..=1.0).contains(&crossover_rate));
+
+        Self {
+            variant: Default::default(),
+            weight,
+            crossover_rate,
+            self_adaptive: false,
+            bounds: None,
+            observer: NoObserver::new(),
+            members: Vec::new(),
+            trials: Vec::new(),
+            generation: 0,
+        }
+    }
+}
+
+impl<G, O> DifferentialEvolution<G, O> {
+    pub fn with_variant(mut self, variant: DifferentialVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Enables jDE self-adaptation: every individual starts with the
+    /// configured F and CR, and then, with probability 0.1 each, gets them
+    /// re-drawn (F from 0.1..1.0, CR from 0.0..1.0) for its next trial;
+    /// parameters survive along with trials that replace their targets.
+    pub fn with_self_adaptation(mut self) -> Self {
+        self.self_adaptive = true;
+        self
+    }
+
+    pub fn with_bounds(mut self, bounds: GeneBounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Registers observer notified about each generation and each trial;
+    /// parents aren't reported, since trials don't have a fixed number of
+    /// them.
+    pub fn with_observer<O2>(
+        self,
+        observer: O2,
+    ) -> DifferentialEvolution<G, O2> {
+        DifferentialEvolution {
+            variant: self.variant,
+            weight: self.weight,
+            crossover_rate: self.crossover_rate,
+            self_adaptive: self.self_adaptive,
+            bounds: self.bounds,
+            observer,
+            members: self.members,
+            trials: self.trials,
+            generation: self.generation,
+        }
+    }
+
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Returns the fittest target seen so far, along with its fitness.
+    pub fn best(&self) -> Option<(&Chromosome<G>, f32)> {
+        self.members
+            .iter()
+            .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+            .map(|member| (&member.chromosome, member.fitness))
+    }
+}
+
+impl<G, O> DifferentialEvolution<G, O>
+where
+    G: Gene,
+    O: EvolutionObserver<G>,
+{
+    /// Lets evaluated trials of the previous generation compete with their
+    /// targets and returns a new batch of trials.
+    ///
+    /// Returned statistics describe `population`.
+    pub fn evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Statistics)
+    where
+        I: Individual<G>,
+    {
+        assert!(population.len() >= 4);
+
+        assert!(
+            !population[0].chromosome().is_empty(),
+            "got an empty chromosome"
+        );
+
+        self.observer.on_generation_start(self.generation);
+        self.select(population);
+
+        let best = self
+            .members
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.fitness.total_cmp(&b.fitness))
+            .map(|(idx, _)| idx)
+            .unwrap();
+
+        self.trials.clear();
+
+        let trials: Vec<_> = (0..self.members.len())
+            .map(|target| {
+                let parameters = self.parameters(rng, target);
+                let chromosome = self.trial(rng, target, best, parameters);
+
+                self.trials.push(parameters);
+
+                let trial = I::from_chromosome(chromosome);
+
+                self.observer
+                    .on_child_created(trial.chromosome(), trial.fitness());
+
+                trial
+            })
+            .collect();
+
+        let statistics = Statistics::new(population);
+
+        self.observer.on_generation_end(self.generation, &statistics);
+        self.generation += 1;
+
+        (trials, statistics)
+    }
+
+    /// Replaces targets with trials that are at least as fit; on the first
+    /// call (or when the population's size changes) `population` simply
+    /// becomes the targets.
+    fn select<I: Individual<G>>(&mut self, population: &[I]) {
+        if self.members.len() != population.len()
+            || self.trials.len() != population.len()
+        {
+            let parameters = Parameters {
+                weight: self.weight,
+                crossover_rate: self.crossover_rate,
+            };
+
+            self.members = population
+                .iter()
+                .map(|individual| Member {
+                    chromosome: individual.chromosome().clone(),
+                    fitness: individual.fitness(),
+                    parameters,
+                })
+                .collect();
+
+            return;
+        }
+
+        for ((member, trial), &parameters) in
+            self.members.iter_mut().zip(population).zip(&self.trials)
+        {
+            if trial.fitness() >= member.fitness {
+                *member = Member {
+                    chromosome: trial.chromosome().clone(),
+                    fitness: trial.fitness(),
+                    parameters,
+                };
+            }
+        }
+    }
+
+    /// Returns F and CR for the trial of given target.
+    fn parameters(&self, rng: &mut dyn RngCore, target: usize) -> Parameters {
+        let mut parameters = self.members[target].parameters;
+
+        if self.self_adaptive {
+            if rng.gen_bool(0.1) {
+                parameters.weight = rng.gen_range(0.1..1.0);
+            }
+
+            if rng.gen_bool(0.1) {
+                parameters.crossover_rate = rng.gen_range(0.0..1.0);
+            }
+        }
+
+        parameters
+    }
+
+    fn trial(
+        &self,
+        rng: &mut dyn RngCore,
+        target: usize,
+        best: usize,
+        parameters: Parameters,
+    ) -> Chromosome<G> {
+        let len = self.members.len();
+
+        // Picks three distinct individuals other than the target
+        let others: Vec<_> = index::sample(rng, len - 1, 3)
+            .into_iter()
+            .map(|idx| if idx >= target { idx + 1 } else { idx })
+            .collect();
+
+        let gene = |idx: usize, gene: usize| {
+            self.members[idx].chromosome[gene].to_f32()
+        };
+
+        let f = parameters.weight;
+        let (r1, r2, r3) = (others[0], others[1], others[2]);
+
+        let mut chromosome = self.members[target].chromosome.clone();
+        let forced = rng.gen_range(0..chromosome.len());
+
+        for (idx, trial_gene) in chromosome.iter_mut().enumerate() {
+            if idx != forced && !rng.gen_bool(parameters.crossover_rate as f64)
+            {
+                continue;
+            }
+
+            let mutant = match self.variant {
+                DifferentialVariant::Rand1Bin => {
+                    gene(r1, idx) + f * (gene(r2, idx) - gene(r3, idx))
+                }
+
+                DifferentialVariant::Best1Bin => {
+                    gene(best, idx) + f * (gene(r1, idx) - gene(r2, idx))
+                }
+
+                DifferentialVariant::CurrentToBest1 => {
+                    gene(target, idx)
+                        + f * (gene(best, idx) - gene(target, idx))
+                        + f * (gene(r1, idx) - gene(r2, idx))
+                }
+            };
+
+            *trial_gene = G::from_f32(mutant);
+        }
+
+        if let Some(bounds) = &self.bounds {
+            bounds.apply(&mut chromosome);
+        }
+
+        chromosome
+    }
+}
+
+impl<G, O, I> Evolver<G, I> for DifferentialEvolution<G, O>
+where
+    G: Gene,
+    O: EvolutionObserver<G>,
+    I: Individual<G>,
+{
+    fn evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Statistics) {
+        DifferentialEvolution::evolve(self, rng, population)
+    }
+
+    fn should_stop(&self) -> bool {
+        self.observer.should_stop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Sphere function: fitness is the negated squared distance from the
+    /// origin, so the optimum is at all zeros.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Sphere {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for Sphere {
+        fn from_chromosome(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            -self.chromosome.iter().map(|gene| gene * gene).sum::<f32>()
+        }
+    }
+
+    fn population(rng: &mut ChaCha8Rng) -> Vec<Sphere> {
+        (0..20)
+            .map(|_| {
+                let genes = (0..5).map(|_| rng.gen_range(-5.0..5.0));
+
+                Sphere::from_chromosome(genes.collect())
+            })
+            .collect()
+    }
+
+    fn optimise(de: DifferentialEvolution) -> f32 {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut de = de;
+        let mut population = population(&mut rng);
+
+        for _ in 0..100 {
+            population = de.evolve(&mut rng, &population).0;
+        }
+
+        // Let the last batch of trials compete as well
+        de.evolve(&mut rng, &population);
+        de.best().unwrap().1
+    }
+
+    #[test]
+    fn test_rand_1_bin() {
+        assert!(optimise(DifferentialEvolution::new(0.5, 0.9)) > -0.01);
+    }
+
+    #[test]
+    fn test_best_1_bin() {
+        let de = DifferentialEvolution::new(0.5, 0.9)
+            .with_variant(DifferentialVariant::Best1Bin);
+
+        assert!(optimise(de) > -0.0001);
+    }
+
+    #[test]
+    fn test_current_to_best_1() {
+        let de = DifferentialEvolution::new(0.5, 0.9)
+            .with_variant(DifferentialVariant::CurrentToBest1);
+
+        assert!(optimise(de) > -0.0001);
+    }
+
+    #[test]
+    fn test_self_adaptation() {
+        let de = DifferentialEvolution::new(0.5, 0.9).with_self_adaptation();
+
+        assert!(optimise(de) > -0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "got an empty chromosome")]
+    fn test_empty_chromosome() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population: Vec<_> = (0..4)
+            .map(|_| Sphere::from_chromosome(Vec::new().into_iter().collect()))
+            .collect();
+
+        DifferentialEvolution::new(0.5, 0.9).evolve(&mut rng, &population);
+    }
+
+    #[test]
+    fn test_targets_never_get_worse() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut de = DifferentialEvolution::new(0.9, 0.9);
+        let mut population = population(&mut rng);
+        let mut best = f32::MIN;
+
+        for _ in 0..20 {
+            population = de.evolve(&mut rng, &population).0;
+
+            let (_, fitness) = de.best().unwrap();
+
+            assert!(fitness >= best);
+            best = fitness;
+        }
+    }
+
+    /// Individual whose fitness gets assigned from outside, like it's done
+    /// in simulations.
+    struct Evaluated {
+        chromosome: Chromosome,
+        fitness: f32,
+    }
+
+    impl Individual for Evaluated {
+        fn from_chromosome(chromosome: Chromosome) -> Self {
+            Self { chromosome, fitness: 0.0 }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            self.fitness
+        }
+    }
+
+    fn evaluate(trials: Vec<Evaluated>, fitness: f32) -> Vec<Evaluated> {
+        trials.into_iter().map(|trial| Evaluated { fitness, ..trial }).collect()
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_deferred_evaluation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut de = DifferentialEvolution::new(0.5, 0.9);
+
+        let initial = evaluate(
+            population(&mut rng)
+                .into_iter()
+                .map(|individual| {
+                    Evaluated::from_chromosome(individual.chromosome)
+                })
+                .collect(),
+            -1.0,
+        );
+
+        // Trials worse than their targets don't replace them
+        let trials = de.evolve(&mut rng, &initial).0;
+        let trials = de.evolve(&mut rng, &evaluate(trials, -2.0)).0;
+
+        assert_eq!(de.best().unwrap().1, -1.0);
+        assert!(initial
+            .iter()
+            .any(|target| &target.chromosome == de.best().unwrap().0));
+
+        // ... while better ones do
+        let trials = evaluate(trials, 1.0);
+
+        de.evolve(&mut rng, &trials);
+
+        assert_eq!(de.best().unwrap().1, 1.0);
+        assert!(trials
+            .iter()
+            .any(|trial| &trial.chromosome == de.best().unwrap().0));
+    }
+
+    #[test]
+    fn test_with_bounds() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let bounds = GeneBounds::uniform(1.0, 2.0);
+
+        let mut de =
+            DifferentialEvolution::new(0.9, 1.0).with_bounds(bounds.clone());
+
+        let mut population = population(&mut rng);
+
+        for _ in 0..5 {
+            population = de.evolve(&mut rng, &population).0;
+
+            assert!(population
+                .iter()
+                .all(|individual| bounds.contains(individual.chromosome())));
+        }
+    }
+}
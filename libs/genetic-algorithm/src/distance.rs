@@ -0,0 +1,109 @@
+use crate::*;
+
+/// Measures how different two chromosomes are.
+pub trait DistanceMetric<G = f32> {
+    /// Returns the distance between given chromosomes:
+    /// - 0.0 = chromosomes are identical
+    /// - larger = chromosomes are more different
+    fn distance(&self, a: &Chromosome<G>, b: &Chromosome<G>) -> f32;
+}
+
+/// Straight-line distance between chromosomes treated as points.
+#[derive(Clone, Debug, Default)]
+pub struct EuclideanDistance;
+
+impl EuclideanDistance {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G: Gene> DistanceMetric<G> for EuclideanDistance {
+    fn distance(&self, a: &Chromosome<G>, b: &Chromosome<G>) -> f32 {
+        assert_eq!(a.len(), b.len());
+
+        a.iter()
+            .zip(b.iter())
+            .map(|(a, b)| (a.to_f32() - b.to_f32()).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    }
+}
+
+/// Sum of absolute differences between genes.
+#[derive(Clone, Debug, Default)]
+pub struct ManhattanDistance;
+
+impl ManhattanDistance {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G: Gene> DistanceMetric<G> for ManhattanDistance {
+    fn distance(&self, a: &Chromosome<G>, b: &Chromosome<G>) -> f32 {
+        assert_eq!(a.len(), b.len());
+
+        a.iter()
+            .zip(b.iter())
+            .map(|(a, b)| (a.to_f32() - b.to_f32()).abs())
+            .sum()
+    }
+}
+
+/// Number of positions at which genes differ; suitable for binary and
+/// permutation chromosomes.
+#[derive(Clone, Debug, Default)]
+pub struct HammingDistance;
+
+impl HammingDistance {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G: PartialEq> DistanceMetric<G> for HammingDistance {
+    fn distance(&self, a: &Chromosome<G>, b: &Chromosome<G>) -> f32 {
+        assert_eq!(a.len(), b.len());
+
+        a.iter().zip(b.iter()).filter(|(a, b)| a != b).count() as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn chromosome<G>(genes: &[G]) -> Chromosome<G>
+    where
+        G: Clone,
+    {
+        genes.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_euclidean() {
+        let a = chromosome(&[1.0, 2.0, 3.0]);
+        let b = chromosome(&[4.0, 6.0, 3.0]);
+
+        assert_relative_eq!(EuclideanDistance::new().distance(&a, &b), 5.0);
+        assert_relative_eq!(EuclideanDistance::new().distance(&a, &a), 0.0);
+    }
+
+    #[test]
+    fn test_manhattan() {
+        let a = chromosome(&[1, 2, 3]);
+        let b = chromosome(&[4, 6, 3]);
+
+        assert_relative_eq!(ManhattanDistance::new().distance(&a, &b), 7.0);
+    }
+
+    #[test]
+    fn test_hamming() {
+        let a = chromosome(&[true, false, true, true]);
+        let b = chromosome(&[true, true, false, true]);
+
+        assert_relative_eq!(HammingDistance::new().distance(&a, &b), 2.0);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::*;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+/// Drives evolution of a population until given [`Termination`] condition
+/// is met.
+///
+/// Works with anything implementing [`Evolver`], so both a plain
+/// [`GeneticAlgorithm`] and custom drivers can be run this way.
+pub struct Evolution<E, G, I> {
+    algorithm: E,
+    population: Vec<I>,
+    termination: Termination,
+    _gene: PhantomData<G>,
+}
+
+/// Outcome of an [`Evolution`] run.
+#[derive(Clone, Debug)]
+pub struct EvolutionResult<I> {
+    best: I,
+    population: Vec<I>,
+    history: Vec<Statistics>,
+}
+
+impl<E, G, I> Evolution<E, G, I>
+where
+    E: Evolver<G, I>,
+    G: Gene,
+    I: Individual<G>,
+{
+    /// Creates a run that, unless told otherwise, stops after 100
+    /// generations.
+    pub fn new(algorithm: E, population: Vec<I>) -> Self {
+        assert!(!population.is_empty());
+
+        Self {
+            algorithm,
+            population,
+            termination: Termination::MaxGenerations(100),
+            _gene: PhantomData,
+        }
+    }
+
+    pub fn with_termination(mut self, termination: Termination) -> Self {
+        self.termination = termination;
+        self
+    }
+
+    /// Evolves the population until the termination condition is met (or
+    /// the algorithm asks to stop early); the condition gets checked against
+    /// the initial population as well, so it's possible for no generation to
+    /// be evolved at all.
+    pub fn run(mut self, rng: &mut dyn RngCore) -> EvolutionResult<I> {
+        // Instant::now() panics on wasm32-unknown-unknown, so the clock is
+        // only read when some condition actually needs it
+        let start = self.termination.has_time_limit().then(Instant::now);
+        let elapsed = || start.map_or(Duration::ZERO, |start| start.elapsed());
+
+        let mut history = vec![Statistics::new(&self.population)];
+        let mut best = fittest(&self.population);
+
+        while !self.algorithm.should_stop()
+            && !self.termination.is_met(&history, elapsed())
+        {
+            self.population = self.algorithm.evolve(rng, &self.population).0;
+
+            let candidate = fittest(&self.population);
+
+            if candidate.fitness() > best.fitness() {
+                best = candidate;
+            }
+
+            history.push(Statistics::new(&self.population));
+        }
+
+        EvolutionResult { best, population: self.population, history }
+    }
+}
+
+impl<I> EvolutionResult<I> {
+    /// Returns the fittest individual seen during the whole run - not
+    /// necessarily a member of the final population.
+    pub fn best(&self) -> &I {
+        &self.best
+    }
+
+    pub fn population(&self) -> &[I] {
+        &self.population
+    }
+
+    /// Returns statistics of every generation, starting with the initial
+    /// population.
+    pub fn history(&self) -> &[Statistics] {
+        &self.history
+    }
+
+    /// Returns number of generations evolved.
+    pub fn generations(&self) -> usize {
+        self.history.len() - 1
+    }
+}
+
+fn fittest<G: Gene, I: Individual<G>>(population: &[I]) -> I {
+    let fittest = population
+        .iter()
+        .max_by(|a, b| a.fitness().total_cmp(&b.fitness()))
+        .expect("got an empty population");
+
+    clone_individual(fittest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::time::Duration;
+
+    fn population(rng: &mut ChaCha8Rng) -> Vec<TestIndividual> {
+        (0..10)
+            .map(|_| {
+                let genes = (0..5).map(|_| rng.gen_range(-1.0..1.0));
+
+                TestIndividual::from_chromosome(genes.collect())
+            })
+            .collect()
+    }
+
+    fn algorithm() -> impl Evolver<f32, TestIndividual> {
+        GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.1),
+        )
+        .with_elitism(1)
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_max_generations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let result = Evolution::new(algorithm(), population)
+            .with_termination(Termination::MaxGenerations(20))
+            .run(&mut rng);
+
+        assert_eq!(result.generations(), 20);
+        assert_eq!(result.history().len(), 21);
+        assert_eq!(result.population().len(), 10);
+
+        let best_fitness = result
+            .history()
+            .iter()
+            .map(Statistics::max_fitness)
+            .fold(f32::MIN, f32::max);
+
+        assert_eq!(result.best().fitness(), best_fitness);
+        assert!(best_fitness > result.history()[0].max_fitness());
+    }
+
+    #[test]
+    fn test_target_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let result = Evolution::new(algorithm(), population)
+            .with_termination(
+                Termination::TargetFitness(3.0)
+                    .or(Termination::MaxGenerations(1000)),
+            )
+            .run(&mut rng);
+
+        assert!(result.generations() < 1000);
+        assert!(result.best().fitness() >= 3.0);
+    }
+
+    #[test]
+    fn test_met_right_away() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        let result = Evolution::new(algorithm(), population.clone())
+            .with_termination(Termination::TimeLimit(Duration::ZERO))
+            .run(&mut rng);
+
+        assert_eq!(result.generations(), 0);
+        assert_eq!(result.population(), population.as_slice());
+    }
+}
@@ -0,0 +1,76 @@
+/// Value that can be stored in a [`Chromosome`](crate::Chromosome).
+///
+/// Wherever genes have to be measured (e.g. to compute diversity or to keep
+/// them within [`GeneBounds`](crate::GeneBounds)), they're looked at as
+/// numbers.
+pub trait Gene: Clone + PartialEq {
+    fn to_f32(&self) -> f32;
+
+    /// Converts given number back into a gene, rounding it if needed.
+    fn from_f32(value: f32) -> Self;
+}
+
+impl Gene for f32 {
+    fn to_f32(&self) -> f32 {
+        *self
+    }
+
+    fn from_f32(value: f32) -> Self {
+        value
+    }
+}
+
+impl Gene for bool {
+    fn to_f32(&self) -> f32 {
+        if *self {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn from_f32(value: f32) -> Self {
+        value >= 0.5
+    }
+}
+
+macro_rules! impl_integer_gene {
+    ($($ty:ty),*) => {
+        $(
+            impl Gene for $ty {
+                fn to_f32(&self) -> f32 {
+                    *self as f32
+                }
+
+                fn from_f32(value: f32) -> Self {
+                    value.round() as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_gene!(i32, i64, u8, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bool() {
+        assert!(bool::from_f32(true.to_f32()));
+        assert!(!bool::from_f32(false.to_f32()));
+        assert!(bool::from_f32(0.7));
+        assert!(!bool::from_f32(0.3));
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(i32::from_f32(-2.6), -3);
+        assert_eq!(i32::from_f32((-3i32).to_f32()), -3);
+
+        // Negative values saturate at zero for unsigned genes
+        assert_eq!(usize::from_f32(-2.6), 0);
+        assert_eq!(usize::from_f32(2.4), 2);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::*;
+
+/// The fittest individuals ever seen, across all generations.
+///
+/// Individuals are cloned (rather than re-created from their chromosomes),
+/// so that fitness computed outside of `Individual::from_chromosome` - e.g.
+/// by running a simulation - gets preserved.
+#[derive(Clone, Debug)]
+pub struct HallOfFame<I, D = EuclideanDistance> {
+    /// Maximum number of individuals kept.
+    capacity: usize,
+    metric: D,
+    /// Distance up to which chromosomes are considered duplicates:
+    /// - 0.0 = only identical chromosomes are duplicates
+    /// - larger = similar chromosomes are duplicates as well
+    tolerance: f32,
+    /// Members, sorted from the fittest one.
+    members: Vec<I>,
+}
+
+impl<I> HallOfFame<I> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+
+        Self {
+            capacity,
+            metric: EuclideanDistance::new(),
+            tolerance: 0.0,
+            members: Vec::new(),
+        }
+    }
+}
+
+impl<I, D> HallOfFame<I, D> {
+    pub fn with_tolerance(mut self, tolerance: f32) -> Self {
+        assert!(tolerance >= 0.0);
+
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_metric<D2>(self, metric: D2) -> HallOfFame<I, D2> {
+        HallOfFame {
+            capacity: self.capacity,
+            metric,
+            tolerance: self.tolerance,
+            members: self.members,
+        }
+    }
+
+    /// Returns members, sorted from the fittest one.
+    pub fn members(&self) -> &[I] {
+        &self.members
+    }
+
+    pub fn best(&self) -> Option<&I> {
+        self.members.first()
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Admits individuals of `population` that are fitter than the current
+    /// members.
+    ///
+    /// When an individual duplicates one of the members, only the fitter of
+    /// them is kept.
+    pub fn update<G>(&mut self, population: &[I])
+    where
+        I: Individual<G> + Clone,
+        D: DistanceMetric<G>,
+    {
+        let mut ranked: Vec<_> = population.iter().collect();
+        ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        for individual in ranked {
+            if self.members.len() == self.capacity
+                && individual.fitness()
+                    <= self.members[self.capacity - 1].fitness()
+            {
+                break;
+            }
+
+            let duplicate = self.members.iter().position(|member| {
+                self.metric
+                    .distance(member.chromosome(), individual.chromosome())
+                    <= self.tolerance
+            });
+
+            match duplicate {
+                Some(idx) => {
+                    if individual.fitness() > self.members[idx].fitness() {
+                        self.members[idx] = individual.clone();
+                    }
+                }
+
+                None => self.members.push(individual.clone()),
+            }
+
+            self.members.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+            self.members.truncate(self.capacity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::from_chromosome(genes.iter().cloned().collect())
+    }
+
+    fn fitnesses(hall_of_fame: &HallOfFame<TestIndividual>) -> Vec<f32> {
+        hall_of_fame.members().iter().map(Individual::fitness).collect()
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_keeps_the_fittest_across_generations() {
+        let mut hall_of_fame = HallOfFame::new(3);
+
+        hall_of_fame.update(&[
+            individual(&[1.0]),
+            individual(&[5.0]),
+            individual(&[3.0]),
+            individual(&[4.0]),
+        ]);
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![5.0, 4.0, 3.0]);
+
+        // Worse generation doesn't push anyone out
+        hall_of_fame.update(&[individual(&[0.0]), individual(&[2.0])]);
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![5.0, 4.0, 3.0]);
+
+        hall_of_fame.update(&[individual(&[6.0]), individual(&[3.5])]);
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![6.0, 5.0, 4.0]);
+        assert_eq!(hall_of_fame.best(), Some(&individual(&[6.0])));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_skips_duplicates() {
+        let mut hall_of_fame = HallOfFame::new(3);
+
+        hall_of_fame.update(&[
+            individual(&[2.0, 2.0]),
+            individual(&[2.0, 2.0]),
+            individual(&[1.0, 2.0]),
+        ]);
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![4.0, 3.0]);
+
+        hall_of_fame.update(&[individual(&[2.0, 2.0])]);
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![4.0, 3.0]);
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let mut hall_of_fame = HallOfFame::new(3).with_tolerance(0.5);
+
+        hall_of_fame
+            .update(&[individual(&[1.0, 1.0]), individual(&[3.0, 3.0])]);
+
+        // [3.0, 3.4] is close enough to [3.0, 3.0] to replace it, while
+        // [1.0, 1.6] is not close enough to [1.0, 1.0]
+        hall_of_fame
+            .update(&[individual(&[3.0, 3.4]), individual(&[1.0, 1.6])]);
+
+        let actual = fitnesses(&hall_of_fame);
+        let expected = vec![6.4, 2.6, 2.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+}
@@ -0,0 +1,549 @@
+use crate::*;
+use rand::seq::index;
+use std::fmt;
+
+/// Anything that can evolve a population by one generation.
+///
+/// Lets [`IslandModel`] mix genetic algorithms with different operators,
+/// whose types differ.
+pub trait Evolver<G, I> {
+    fn evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Statistics);
+
+    /// Tells whether evolution should stop early, e.g. because an observer
+    /// asked for it.
+    fn should_stop(&self) -> bool {
+        false
+    }
+}
+
+impl<S, C, M, R, N, O, F, G, I> Evolver<G, I>
+    for GeneticAlgorithm<S, C, M, R, N, O, F, G>
+where
+    S: SelectionMethod,
+    C: CrossoverMethod<G>,
+    M: MutationMethod<G>,
+    R: ReplacementMethod,
+    N: NichingMethod<G>,
+    O: EvolutionObserver<G>,
+    F: FitnessScaling,
+    G: Gene,
+    I: Individual<G>,
+{
+    fn evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Statistics) {
+        GeneticAlgorithm::evolve(self, rng, population)
+    }
+
+    fn should_stop(&self) -> bool {
+        self.observer.should_stop()
+    }
+}
+
+/// Which islands migrants travel to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MigrationTopology {
+    /// Each island sends migrants to the next one, the last one to the
+    /// first one.
+    #[default]
+    Ring,
+    /// Each island sends migrants to all the other islands.
+    FullyConnected,
+    /// Each island sends migrants to another island picked at random on
+    /// every migration.
+    Random,
+}
+
+/// Which individuals leave their island.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MigrantSelection {
+    /// The fittest individuals.
+    #[default]
+    Best,
+    /// Individuals picked at random.
+    Random,
+}
+
+/// Evolves several populations side by side, letting them exchange
+/// individuals every now and then.
+///
+/// Populations that evolve separately tend to converge to different
+/// solutions, so they maintain diversity much longer than a single
+/// population would; migration then lets good genes spread. Arriving
+/// migrants replace the least fit individuals of their new island's next
+/// generation, i.e. ones that already survived replacement.
+pub struct IslandModel<G, I> {
+    islands: Vec<Island<G, I>>,
+    topology: MigrationTopology,
+    /// Number of generations between migrations:
+    /// - 1 = migration happens every generation
+    /// - 10 = migration happens every 10th generation
+    interval: usize,
+    /// Number of individuals each island sends to each of its destinations.
+    migrants: usize,
+    migrant_selection: MigrantSelection,
+    /// Number of generations evolved so far.
+    generation: usize,
+}
+
+struct Island<G, I> {
+    algorithm: Box<dyn Evolver<G, I>>,
+    population: Vec<I>,
+}
+
+impl<G, I: fmt::Debug> fmt::Debug for IslandModel<G, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IslandModel")
+            .field("islands", &self.islands)
+            .field("topology", &self.topology)
+            .field("interval", &self.interval)
+            .field("migrants", &self.migrants)
+            .field("migrant_selection", &self.migrant_selection)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+impl<G, I: fmt::Debug> fmt::Debug for Island<G, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Algorithms are type-erased, so there's nothing to print about them
+        f.debug_struct("Island")
+            .field("population", &self.population)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<G, I> IslandModel<G, I>
+where
+    G: Gene,
+    I: Individual<G>,
+{
+    pub fn new() -> Self {
+        Self {
+            islands: Vec::new(),
+            topology: Default::default(),
+            interval: 10,
+            migrants: 1,
+            migrant_selection: Default::default(),
+            generation: 0,
+        }
+    }
+
+    pub fn with_island(
+        mut self,
+        algorithm: impl Evolver<G, I> + 'static,
+        population: Vec<I>,
+    ) -> Self {
+        assert!(!population.is_empty());
+
+        self.islands
+            .push(Island { algorithm: Box::new(algorithm), population });
+
+        self
+    }
+
+    pub fn with_topology(mut self, topology: MigrationTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn with_migration_interval(mut self, interval: usize) -> Self {
+        assert!(interval > 0);
+
+        self.interval = interval;
+        self
+    }
+
+    pub fn with_migrants(mut self, migrants: usize) -> Self {
+        self.migrants = migrants;
+        self
+    }
+
+    pub fn with_migrant_selection(
+        mut self,
+        migrant_selection: MigrantSelection,
+    ) -> Self {
+        self.migrant_selection = migrant_selection;
+        self
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn len(&self) -> usize {
+        self.islands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.islands.is_empty()
+    }
+
+    /// Returns current population of given island.
+    pub fn population(&self, island: usize) -> &[I] {
+        &self.islands[island].population
+    }
+
+    /// Returns the fittest individual across all islands.
+    pub fn best(&self) -> Option<&I> {
+        self.islands
+            .iter()
+            .flat_map(|island| &island.population)
+            .max_by(|a, b| a.fitness().total_cmp(&b.fitness()))
+    }
+
+    /// Evolves every island by one generation and then, if it's time,
+    /// migrates individuals between them.
+    ///
+    /// Returns statistics of each island, in the order islands were added.
+    pub fn evolve(&mut self, rng: &mut dyn RngCore) -> Vec<Statistics> {
+        let statistics = self
+            .islands
+            .iter_mut()
+            .map(|island| {
+                let (population, statistics) =
+                    island.algorithm.evolve(rng, &island.population);
+
+                island.population = population;
+                statistics
+            })
+            .collect();
+
+        self.generation += 1;
+
+        if self.generation % self.interval == 0 {
+            self.migrate(rng);
+        }
+
+        statistics
+    }
+
+    fn migrate(&mut self, rng: &mut dyn RngCore) {
+        let islands = self.islands.len();
+
+        if islands < 2 || self.migrants == 0 {
+            return;
+        }
+
+        let mut arrivals: Vec<Vec<I>> = (0..islands).map(|_| vec![]).collect();
+
+        for source in 0..islands {
+            for target in self.destinations(rng, source) {
+                let migrants = self.pick_migrants(rng, source);
+
+                arrivals[target].extend(migrants);
+            }
+        }
+
+        for (island, arrivals) in self.islands.iter_mut().zip(arrivals) {
+            let population = &mut island.population;
+
+            let mut worst: Vec<_> = (0..population.len()).collect();
+            worst.sort_by(|&a, &b| {
+                population[a].fitness().total_cmp(&population[b].fitness())
+            });
+
+            for (idx, migrant) in worst.into_iter().zip(arrivals) {
+                population[idx] = migrant;
+            }
+        }
+    }
+
+    fn destinations(&self, rng: &mut dyn RngCore, source: usize) -> Vec<usize> {
+        let islands = self.islands.len();
+
+        match self.topology {
+            MigrationTopology::Ring => vec![(source + 1) % islands],
+
+            MigrationTopology::FullyConnected => {
+                (0..islands).filter(|&target| target != source).collect()
+            }
+
+            MigrationTopology::Random => {
+                // Picks among all islands but the source one
+                let target = rng.gen_range(0..islands - 1);
+
+                vec![if target >= source { target + 1 } else { target }]
+            }
+        }
+    }
+
+    fn pick_migrants(&self, rng: &mut dyn RngCore, source: usize) -> Vec<I> {
+        let population = &self.islands[source].population;
+        let migrants = self.migrants.min(population.len());
+
+        let picked = match self.migrant_selection {
+            MigrantSelection::Best => {
+                let mut ranked: Vec<_> = (0..population.len()).collect();
+
+                ranked.sort_by(|&a, &b| {
+                    population[b].fitness().total_cmp(&population[a].fitness())
+                });
+
+                ranked.truncate(migrants);
+                ranked
+            }
+
+            MigrantSelection::Random => {
+                index::sample(rng, population.len(), migrants).into_vec()
+            }
+        };
+
+        picked
+            .into_iter()
+            .map(|idx| clone_individual(&population[idx]))
+            .collect()
+    }
+}
+
+impl<G, I> Default for IslandModel<G, I>
+where
+    G: Gene,
+    I: Individual<G>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Leaves populations as they are, so that only migration changes them.
+    struct Frozen;
+
+    impl Evolver<f32, TestIndividual> for Frozen {
+        fn evolve(
+            &mut self,
+            _rng: &mut dyn RngCore,
+            population: &[TestIndividual],
+        ) -> (Vec<TestIndividual>, Statistics) {
+            (population.to_vec(), Statistics::new(population))
+        }
+    }
+
+    fn individual(fitness: f32) -> TestIndividual {
+        TestIndividual::from_chromosome(vec![fitness].into_iter().collect())
+    }
+
+    fn island(fitnesses: &[f32]) -> Vec<TestIndividual> {
+        fitnesses.iter().map(|&fitness| individual(fitness)).collect()
+    }
+
+    fn frozen_model(
+        topology: MigrationTopology,
+    ) -> IslandModel<f32, TestIndividual> {
+        IslandModel::new()
+            .with_island(Frozen, island(&[1.0, 2.0, 3.0]))
+            .with_island(Frozen, island(&[10.0, 20.0, 30.0]))
+            .with_island(Frozen, island(&[100.0, 200.0, 300.0]))
+            .with_topology(topology)
+            .with_migration_interval(2)
+    }
+
+    fn fitnesses(model: &IslandModel<f32, TestIndividual>) -> Vec<Vec<f32>> {
+        (0..model.len())
+            .map(|island| {
+                model
+                    .population(island)
+                    .iter()
+                    .map(Individual::fitness)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_ring_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut model = frozen_model(MigrationTopology::Ring);
+
+        // Nothing happens before the migration interval passes
+        model.evolve(&mut rng);
+
+        assert_eq!(
+            fitnesses(&model),
+            vec![
+                vec![1.0, 2.0, 3.0],
+                vec![10.0, 20.0, 30.0],
+                vec![100.0, 200.0, 300.0]
+            ]
+        );
+
+        model.evolve(&mut rng);
+
+        assert_eq!(model.generation(), 2);
+        assert_eq!(
+            fitnesses(&model),
+            vec![
+                vec![300.0, 2.0, 3.0],
+                vec![3.0, 20.0, 30.0],
+                vec![30.0, 200.0, 300.0]
+            ]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_fully_connected_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut model = frozen_model(MigrationTopology::FullyConnected);
+
+        model.evolve(&mut rng);
+        model.evolve(&mut rng);
+
+        assert_eq!(
+            fitnesses(&model),
+            vec![
+                vec![30.0, 300.0, 3.0],
+                vec![3.0, 300.0, 30.0],
+                vec![3.0, 30.0, 300.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_random_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let model = frozen_model(MigrationTopology::Random);
+
+        // Number of times each island got picked as a destination of
+        // island #1
+        let mut histogram = [0; 3];
+
+        for _ in 0..1000 {
+            for target in model.destinations(&mut rng, 1) {
+                histogram[target] += 1;
+            }
+        }
+
+        assert_eq!(histogram, [490, 0, 510]);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_random_migrant_selection() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let model = frozen_model(MigrationTopology::Ring)
+            .with_migrants(2)
+            .with_migrant_selection(MigrantSelection::Random);
+
+        let migrants: Vec<_> = model
+            .pick_migrants(&mut rng, 1)
+            .iter()
+            .map(Individual::fitness)
+            .collect();
+
+        assert_eq!(migrants, vec![10.0, 30.0]);
+    }
+
+    #[test]
+    fn test_islands_with_different_operators() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = |rng: &mut ChaCha8Rng| -> Vec<TestIndividual> {
+            (0..10)
+                .map(|_| {
+                    let genes = (0..5).map(|_| rng.gen_range(-1.0..1.0));
+
+                    TestIndividual::from_chromosome(genes.collect())
+                })
+                .collect()
+        };
+
+        let first = population(&mut rng);
+        let second = population(&mut rng);
+
+        let mut model = IslandModel::new()
+            .with_island(
+                GeneticAlgorithm::new(
+                    TournamentSelection::new(2, 1.0),
+                    UniformCrossover::new(),
+                    GaussianMutation::new(0.5, 0.1),
+                ),
+                first,
+            )
+            .with_island(
+                GeneticAlgorithm::new(
+                    RankSelection::linear(2.0),
+                    BlendCrossover::new(0.5),
+                    AnnealingMutation::new(0.5, 0.5, 0.9),
+                )
+                .with_elitism(1),
+                second,
+            )
+            .with_migration_interval(5);
+
+        let initial = model.best().unwrap().fitness();
+
+        for _ in 0..20 {
+            let statistics = model.evolve(&mut rng);
+
+            assert_eq!(statistics.len(), 2);
+        }
+
+        assert!(model.best().unwrap().fitness() > initial);
+    }
+
+    #[test]
+    fn test_migration_with_replacement() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let island = |genes: &[[f32; 3]]| -> Vec<TestIndividual> {
+            genes
+                .iter()
+                .map(|genes| {
+                    TestIndividual::from_chromosome(
+                        genes.iter().cloned().collect(),
+                    )
+                })
+                .collect()
+        };
+
+        let algorithm = || {
+            GeneticAlgorithm::new(
+                TournamentSelection::new(2, 1.0),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_replacement(MuPlusLambdaReplacement::new(6))
+        };
+
+        let mut model = IslandModel::new()
+            .with_island(
+                algorithm(),
+                island(&[[0.5, 0.0, 0.0], [1.0, 1.0, 2.0], [1.0, 2.0, 1.0]]),
+            )
+            .with_island(
+                algorithm(),
+                island(&[[0.0, 0.0, 1.0], [2.0, 1.0, 1.0], [1.0, 2.0, 4.0]]),
+            )
+            .with_migration_interval(2);
+
+        let mut best = model.best().unwrap().fitness();
+
+        for _ in 0..10 {
+            model.evolve(&mut rng);
+
+            // Migrants join the survivors, so islands keep their size and
+            // - with (μ+λ) - the best individual never gets lost
+            assert_eq!(model.population(0).len(), 3);
+            assert_eq!(model.population(1).len(), 3);
+            assert!(model.best().unwrap().fitness() >= best);
+
+            best = model.best().unwrap().fitness();
+        }
+
+        assert!(format!("{:?}", model).starts_with("IslandModel"));
+    }
+}
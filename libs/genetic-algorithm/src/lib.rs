@@ -1,67 +1,1201 @@
+mod bounds;
 mod chromosome;
+mod cma_es;
 mod crossover;
+mod differential_evolution;
+mod distance;
+mod evolution;
+mod gene;
+mod hall_of_fame;
+mod island;
+mod map_elites;
 mod mutation;
+mod niching;
+mod novelty;
+mod nsga2;
+mod observer;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod penalty;
+#[cfg(feature = "serde")]
+mod population;
+mod replacement;
+mod scaling;
 mod selection;
+mod statistics;
+mod termination;
 
+pub use bounds::*;
 pub use chromosome::*;
+pub use cma_es::*;
 pub use crossover::*;
+pub use differential_evolution::*;
+pub use distance::*;
+pub use evolution::*;
+pub use gene::*;
+pub use hall_of_fame::*;
+pub use island::*;
+pub use map_elites::*;
 pub use mutation::*;
+pub use niching::*;
+pub use novelty::*;
+pub use nsga2::*;
+pub use observer::*;
+pub use penalty::*;
+#[cfg(feature = "serde")]
+pub use population::*;
+pub use replacement::*;
+pub use scaling::*;
 pub use selection::*;
+pub use statistics::*;
+pub use termination::*;
 
 use rand::prelude::*;
+use std::rc::Rc;
 
+/// Classic genetic algorithm: selection, crossover, mutation and
+/// replacement.
+///
+/// Fitness can be computed either in `Individual::from_chromosome` or
+/// outside, after [`GeneticAlgorithm::evolve`] returns (e.g. by running a
+/// simulation) - though the latter rules out some replacement methods, see
+/// [`ReplacementMethod`].
 #[derive(Debug)]
-pub struct GeneticAlgorithm<S, C, M> {
+pub struct GeneticAlgorithm<
+    S,
+    C,
+    M,
+    R = GenerationalReplacement,
+    N = NoNiching,
+    O = NoObserver,
+    F = NoScaling,
+    G = f32,
+> {
     selection_method: S,
     crossover_method: C,
     mutation_method: M,
+    replacement_method: R,
+    niching_method: N,
+    observer: O,
+    scaling: F,
+    /// Number of the fittest individuals copied unchanged into the next
+    /// generation.
+    elitism: usize,
+    /// Optional bounds enforced on every child, after both crossover and
+    /// mutation.
+    bounds: Option<GeneBounds>,
+    /// Number of generations evolved so far.
+    generation: usize,
+    /// Offspring of the previous generation, until they come back
+    /// evaluated.
+    pending: Option<Pending<G>>,
 }
 
-impl<S, C, M> GeneticAlgorithm<S, C, M>
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Pending<G> {
+    /// Offspring that made it into the returned population: their
+    /// position, chromosome (to recognize them by) and fitness of their
+    /// fitter parent.
+    offspring: Vec<(usize, Chromosome<G>, f32)>,
+    /// Number of offspring that got replaced right away.
+    replaced: usize,
+    /// Number of replaced offspring that were fitter than both of their
+    /// parents.
+    replaced_successes: usize,
+}
+
+impl<S, C, M, G>
+    GeneticAlgorithm<
+        S,
+        C,
+        M,
+        GenerationalReplacement,
+        NoNiching,
+        NoObserver,
+        NoScaling,
+        G,
+    >
 where
     S: SelectionMethod,
-    C: CrossoverMethod,
-    M: MutationMethod,
 {
     pub fn new(
         selection_method: S,
         crossover_method: C,
         mutation_method: M,
     ) -> Self {
-        Self { selection_method, crossover_method, mutation_method }
+        Self {
+            selection_method,
+            crossover_method,
+            mutation_method,
+            replacement_method: GenerationalReplacement::new(),
+            niching_method: NoNiching::new(),
+            observer: NoObserver::new(),
+            scaling: NoScaling::new(),
+            elitism: 0,
+            bounds: None,
+            generation: 0,
+            pending: None,
+        }
     }
+}
 
-    pub fn evolve<'a, I: Individual>(
-        &self,
+impl<S, C, M, R, N, O, F, G> GeneticAlgorithm<S, C, M, R, N, O, F, G>
+where
+    S: SelectionMethod,
+    R: ReplacementMethod,
+    F: FitnessScaling,
+{
+    pub fn with_elitism(mut self, elitism: usize) -> Self {
+        self.elitism = elitism;
+        self
+    }
+
+    pub fn with_replacement<R2: ReplacementMethod>(
+        self,
+        replacement_method: R2,
+    ) -> GeneticAlgorithm<S, C, M, R2, N, O, F, G> {
+        GeneticAlgorithm {
+            selection_method: self.selection_method,
+            crossover_method: self.crossover_method,
+            mutation_method: self.mutation_method,
+            replacement_method,
+            niching_method: self.niching_method,
+            observer: self.observer,
+            scaling: self.scaling,
+            elitism: self.elitism,
+            bounds: self.bounds,
+            generation: self.generation,
+            pending: self.pending,
+        }
+    }
+
+    /// Makes selection see fitness adjusted by given niching method, so that
+    /// the population spreads over several niches.
+    pub fn with_niching<N2>(
+        self,
+        niching_method: N2,
+    ) -> GeneticAlgorithm<S, C, M, R, N2, O, F, G> {
+        GeneticAlgorithm {
+            selection_method: self.selection_method,
+            crossover_method: self.crossover_method,
+            mutation_method: self.mutation_method,
+            replacement_method: self.replacement_method,
+            niching_method,
+            observer: self.observer,
+            scaling: self.scaling,
+            elitism: self.elitism,
+            bounds: self.bounds,
+            generation: self.generation,
+            pending: self.pending,
+        }
+    }
+
+    /// Registers observer notified about each step of evolution; several
+    /// observers can be registered at once through [`Observers`].
+    pub fn with_observer<O2>(
+        self,
+        observer: O2,
+    ) -> GeneticAlgorithm<S, C, M, R, N, O2, F, G> {
+        GeneticAlgorithm {
+            selection_method: self.selection_method,
+            crossover_method: self.crossover_method,
+            mutation_method: self.mutation_method,
+            replacement_method: self.replacement_method,
+            niching_method: self.niching_method,
+            observer,
+            scaling: self.scaling,
+            elitism: self.elitism,
+            bounds: self.bounds,
+            generation: self.generation,
+            pending: self.pending,
+        }
+    }
+
+    /// Makes selection see fitness transformed by given scaling, e.g. to
+    /// control selection pressure.
+    pub fn with_scaling<F2: FitnessScaling>(
+        self,
+        scaling: F2,
+    ) -> GeneticAlgorithm<S, C, M, R, N, O, F2, G> {
+        GeneticAlgorithm {
+            selection_method: self.selection_method,
+            crossover_method: self.crossover_method,
+            mutation_method: self.mutation_method,
+            replacement_method: self.replacement_method,
+            niching_method: self.niching_method,
+            observer: self.observer,
+            scaling,
+            elitism: self.elitism,
+            bounds: self.bounds,
+            generation: self.generation,
+            pending: self.pending,
+        }
+    }
+
+    pub fn with_bounds(mut self, bounds: GeneBounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Sets the number of generations evolved so far.
+    pub fn with_generation(mut self, generation: usize) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Evolves given population by one generation, returning the next
+    /// generation along with its statistics; see
+    /// [`GeneticAlgorithm::try_evolve`] for a fallible version.
+    ///
+    /// When `population` is the one returned by the previous call, its
+    /// offspring are recognized by their chromosomes, so that adaptive
+    /// mutation methods can learn how they did once evaluated; offspring
+    /// that aren't there anymore (e.g. because migration replaced them) are
+    /// skipped.
+    pub fn evolve<I>(
+        &mut self,
         rng: &mut dyn RngCore,
-        population: &'a [I],
-    ) -> Vec<I> {
-        assert!(!population.is_empty());
+        population: &[I],
+    ) -> (Vec<I>, Statistics)
+    where
+        G: Gene,
+        I: Individual<G>,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+        N: NichingMethod<G>,
+        O: EvolutionObserver<G>,
+    {
+        match self.try_evolve(rng, population) {
+            Ok(evolved) => evolved,
+            Err(err) => panic!("{}", err),
+        }
+    }
 
-        (0..population.len())
-            .map(|_| {
-                // selection
-                let parent_a =
-                    self.selection_method.select(rng, population).chromosome();
-                let parent_b =
-                    self.selection_method.select(rng, population).chromosome();
+    /// Works like [`GeneticAlgorithm::evolve`], but returns an error when
+    /// parents can't be selected, e.g. because the population is empty (or
+    /// smaller than elitism) or a fitness-proportionate selection method got
+    /// negative fitness.
+    pub fn try_evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), SelectionError>
+    where
+        G: Gene,
+        I: Individual<G>,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+        N: NichingMethod<G>,
+        O: EvolutionObserver<G>,
+    {
+        self.check(population)?;
+
+        let success_rate = self.success_rate(population);
+        let ranked = self.rank(population);
+        let parents = self.select_parents(rng, population, &ranked)?;
 
-                // crossover
-                let mut child =
-                    self.crossover_method.crossover(rng, parent_a, parent_b);
+        self.adapt(success_rate);
 
-                // mutation
-                self.mutation_method.mutate(rng, &mut child);
+        let breeder = self.breeder();
 
-                // convert `Chromosome` back into `Individual`.
-                I::from_chromosome(child)
+        let offspring = parents
+            .chunks(2)
+            .map(|parents| breeder.breed(rng, parents[0], parents[1]))
+            .collect();
+
+        Ok(self.next_generation(&ranked, offspring))
+    }
+
+    /// Works like [`Self::evolve`], but first records the fittest
+    /// individuals of `population` in `hall_of_fame`, so that they survive
+    /// even if evolution loses them later.
+    pub fn evolve_with_hall_of_fame<I, D>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        hall_of_fame: &mut HallOfFame<I, D>,
+    ) -> (Vec<I>, Statistics)
+    where
+        G: Gene,
+        I: Individual<G> + Clone,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+        N: NichingMethod<G>,
+        O: EvolutionObserver<G>,
+        D: DistanceMetric<G>,
+    {
+        hall_of_fame.update(population);
+        self.evolve(rng, population)
+    }
+
+    fn check<I>(&self, population: &[I]) -> Result<(), SelectionError> {
+        if population.is_empty() {
+            return Err(SelectionError::EmptyPopulation);
+        }
+
+        if population.len() < self.elitism {
+            return Err(SelectionError::TooManyElites {
+                elitism: self.elitism,
+                population: population.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the fraction of offspring of the previous generation that
+    /// turned out fitter than both of their parents, unless `population`
+    /// isn't the one returned back then.
+    fn success_rate<I>(&self, population: &[I]) -> Option<f32>
+    where
+        G: Gene,
+        I: Individual<G>,
+    {
+        let pending = self.pending.as_ref()?;
+
+        let returned: Vec<_> = pending
+            .offspring
+            .iter()
+            .filter_map(|(position, chromosome, fitness)| {
+                let child = population.get(*position)?;
+
+                if child.chromosome() == chromosome {
+                    Some(child.fitness() > *fitness)
+                } else {
+                    None
+                }
             })
-            .collect()
+            .collect();
+
+        if returned.is_empty() && !pending.offspring.is_empty() {
+            return None;
+        }
+
+        let offspring = returned.len() + pending.replaced;
+
+        if offspring == 0 {
+            return Some(0.0);
+        }
+
+        let successes = returned.iter().filter(|&&success| success).count()
+            + pending.replaced_successes;
+
+        Some(successes as f32 / offspring as f32)
+    }
+
+    /// Lets the mutation method adjust itself to the generation that's
+    /// about to be bred.
+    fn adapt(&mut self, success_rate: Option<f32>)
+    where
+        M: MutationMethod<G>,
+    {
+        if let Some(success_rate) = success_rate {
+            self.mutation_method.adapt(self.generation, success_rate);
+        }
+    }
+
+    /// Returns population sorted from the fittest individual.
+    fn rank<'a, I>(&self, population: &'a [I]) -> Vec<&'a I>
+    where
+        I: Individual<G>,
+    {
+        let mut ranked: Vec<_> = population.iter().collect();
+        ranked.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+        ranked
+    }
+
+    /// Returns parents for all offspring, in pairs.
+    fn select_parents<'a, I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        ranked: &[&I],
+    ) -> Result<Vec<&'a I>, SelectionError>
+    where
+        I: Individual<G>,
+        N: NichingMethod<G>,
+        O: EvolutionObserver<G>,
+    {
+        self.observer.on_generation_start(self.generation);
+
+        let count = ranked.len() - self.elitism;
+        let offspring_count = self.replacement_method.offspring_count(count);
+
+        let fitnesses = self.niching_method.adjust(population);
+        let fitnesses = self.scaling.scale(self.generation, &fitnesses);
+        let adjusted = Adjusted::wrap(population, fitnesses);
+
+        let parents: Vec<_> = self
+            .selection_method
+            .try_select_many(rng, &adjusted, 2 * offspring_count)?
+            .into_iter()
+            .map(|parent| parent.individual)
+            .collect();
+
+        let chromosomes: Vec<_> =
+            parents.iter().map(|parent| parent.chromosome()).collect();
+
+        self.observer.on_parents_selected(&chromosomes);
+
+        Ok(parents)
     }
+
+    fn breeder(&self) -> Breeder<'_, C, M> {
+        Breeder {
+            crossover_method: &self.crossover_method,
+            mutation_method: &self.mutation_method,
+            bounds: self.bounds.as_ref(),
+        }
+    }
+
+    fn next_generation<I>(
+        &mut self,
+        ranked: &[&I],
+        offspring: Vec<(I, f32)>,
+    ) -> (Vec<I>, Statistics)
+    where
+        G: Gene,
+        I: Individual<G>,
+        O: EvolutionObserver<G>,
+    {
+        for (child, _) in &offspring {
+            self.observer.on_child_created(child.chromosome(), child.fitness());
+        }
+
+        let (elites, rest) = ranked.split_at(self.elitism);
+
+        let bred = offspring.len();
+        let successes = offspring
+            .iter()
+            .filter(|(child, fitness)| child.fitness() > *fitness)
+            .count();
+
+        let mut evolved_population: Vec<_> =
+            elites.iter().map(|elite| clone_individual(*elite)).collect();
+
+        let mut returned = Vec::new();
+        let mut returned_successes = 0;
+
+        for survivor in self.replace(rest, offspring) {
+            match survivor {
+                Contender::Parent(parent) => {
+                    evolved_population.push(clone_individual(parent));
+                }
+
+                Contender::Child(child) => {
+                    let (child, fitness) = Rc::try_unwrap(child)
+                        .unwrap_or_else(|child| {
+                            (clone_individual(&child.0), child.1)
+                        });
+
+                    if child.fitness() > fitness {
+                        returned_successes += 1;
+                    }
+
+                    returned.push((
+                        evolved_population.len(),
+                        child.chromosome().clone(),
+                        fitness,
+                    ));
+
+                    evolved_population.push(child);
+                }
+            }
+        }
+
+        self.pending = Some(Pending {
+            replaced: bred.saturating_sub(returned.len()),
+            replaced_successes: successes.saturating_sub(returned_successes),
+            offspring: returned,
+        });
+
+        let statistics = Statistics::new(&evolved_population);
+
+        self.observer.on_generation_end(self.generation, &statistics);
+        self.generation += 1;
+
+        (evolved_population, statistics)
+    }
+
+    /// Lets `offspring` compete with `parents` for their places in the next
+    /// generation.
+    fn replace<'a, I>(
+        &self,
+        parents: &[&'a I],
+        offspring: Vec<(I, f32)>,
+    ) -> Vec<Contender<'a, I>>
+    where
+        G: Gene,
+        I: Individual<G>,
+    {
+        let contenders: Vec<_> =
+            parents.iter().map(|&parent| Contender::Parent(parent)).collect();
+
+        let contenders: Vec<_> = contenders.iter().collect();
+
+        let offspring = offspring
+            .into_iter()
+            .map(|child| Contender::Child(Rc::new(child)))
+            .collect();
+
+        self.replacement_method.replace(&contenders, offspring, parents.len())
+    }
+}
+
+/// Operators offspring get bred with, borrowed apart from the rest of the
+/// algorithm - so that breeding can happen on many threads, even if e.g.
+/// the observer can't be shared between them.
+struct Breeder<'a, C, M> {
+    crossover_method: &'a C,
+    mutation_method: &'a M,
+    bounds: Option<&'a GeneBounds>,
 }
 
-pub trait Individual {
-    fn from_chromosome(chromosome: Chromosome) -> Self;
-    fn chromosome(&self) -> &Chromosome;
+impl<'a, C, M> Breeder<'a, C, M> {
+    /// Creates a child of given parents, returning also fitness of the
+    /// fitter parent, for the child to be compared with once evaluated.
+    fn breed<G, P, I>(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &P,
+        parent_b: &P,
+    ) -> (I, f32)
+    where
+        G: Gene,
+        P: Individual<G>,
+        I: Individual<G>,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        let chromosome_a = parent_a.chromosome();
+        let chromosome_b = parent_b.chromosome();
+
+        // crossover
+        let mut child =
+            self.crossover_method.crossover(rng, chromosome_a, chromosome_b);
+
+        child.inherit_step_sizes(chromosome_a, chromosome_b);
+        self.apply_bounds(&mut child);
+
+        // mutation
+        self.mutation_method.mutate(rng, &mut child);
+        self.apply_bounds(&mut child);
+
+        // convert `Chromosome` back into `Individual`.
+        let child = I::from_chromosome(child);
+
+        (child, parent_a.fitness().max(parent_b.fitness()))
+    }
+
+    fn apply_bounds<G: Gene>(&self, child: &mut Chromosome<G>) {
+        if let Some(bounds) = self.bounds {
+            bounds.apply(child);
+        }
+    }
+}
+
+pub trait Individual<G = f32> {
+    fn from_chromosome(chromosome: Chromosome<G>) -> Self;
+    fn chromosome(&self) -> &Chromosome<G>;
     fn fitness(&self) -> f32;
 }
+
+#[cfg(test)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum TestIndividual<G = f32> {
+    WithChromosome { chromosome: Chromosome<G> },
+    WithFitness { fitness: f32 },
+}
+
+#[cfg(test)]
+impl TestIndividual {
+    pub fn new(fitness: f32) -> Self {
+        Self::WithFitness { fitness }
+    }
+}
+
+#[cfg(test)]
+impl<G: Gene> Individual<G> for TestIndividual<G> {
+    fn from_chromosome(chromosome: Chromosome<G>) -> Self {
+        Self::WithChromosome { chromosome }
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        match self {
+            Self::WithChromosome { chromosome } => chromosome,
+            Self::WithFitness { .. } => {
+                panic!("not supported for TestIndividual::WithFitness")
+            }
+        }
+    }
+
+    fn fitness(&self) -> f32 {
+        match self {
+            Self::WithChromosome { chromosome } => {
+                chromosome.iter().map(Gene::to_f32).sum()
+            }
+            Self::WithFitness { fitness } => *fitness,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::from_chromosome(genes.iter().cloned().collect())
+    }
+
+    type Algorithm<M, R> =
+        GeneticAlgorithm<TournamentSelection, UniformCrossover, M, R>;
+
+    /// Individual whose fitness is the sum of its genes, but becomes known
+    /// only once [`Deferred::evaluate`] gets called.
+    #[derive(Clone, Debug)]
+    struct Deferred {
+        chromosome: Chromosome,
+        fitness: f32,
+    }
+
+    impl Deferred {
+        fn new(genes: &[f32]) -> Self {
+            let mut individual =
+                Self::from_chromosome(genes.iter().cloned().collect());
+
+            individual.evaluate();
+            individual
+        }
+
+        fn evaluate(&mut self) {
+            self.fitness = self.chromosome.iter().sum();
+        }
+    }
+
+    impl Individual for Deferred {
+        fn from_chromosome(chromosome: Chromosome) -> Self {
+            Self { chromosome, fitness: 0.0 }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            self.fitness
+        }
+    }
+
+    fn chromosomes<I: Individual>(population: &[I]) -> Vec<&Chromosome> {
+        population.iter().map(Individual::chromosome).collect()
+    }
+
+    fn best_fitness(population: &[TestIndividual]) -> f32 {
+        population.iter().map(Individual::fitness).fold(f32::MIN, f32::max)
+    }
+
+    #[test]
+    fn test_evolve_with_elitism() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_elitism(2);
+
+        let mut population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 2.0]),
+            individual(&[1.0, 2.0, 1.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        for _ in 0..10 {
+            let best = best_fitness(&population);
+            let (evolved, _) = ga.evolve(&mut rng, &population);
+
+            assert_eq!(evolved.len(), population.len());
+            assert!(best_fitness(&evolved) >= best);
+
+            population = evolved;
+        }
+    }
+
+    #[test]
+    fn test_evolve_copies_elites_unchanged() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            GaussianMutation::new(1.0, 1.0),
+        )
+        .with_elitism(1);
+
+        let population = vec![
+            individual(&[1.0, 1.0, 2.0]),
+            individual(&[3.0, 2.0, 4.0]),
+            individual(&[1.0, 2.0, 1.0]),
+        ];
+
+        let (evolved, _) = ga.evolve(&mut rng, &population);
+
+        assert_eq!(evolved[0], individual(&[3.0, 2.0, 4.0]));
+    }
+
+    #[test]
+    fn test_evolve_statistics() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let population = vec![
+            individual(&[0.5, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 2.0]),
+            individual(&[1.0, 2.0, 1.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        let (evolved, stats) = ga.evolve(&mut rng, &population);
+        let expected = Statistics::new(&evolved);
+
+        // Statistics describe the outgoing generation
+        assert_relative_eq!(stats.min_fitness(), expected.min_fitness());
+        assert_relative_eq!(stats.max_fitness(), expected.max_fitness());
+        assert_relative_eq!(stats.mean_fitness(), expected.mean_fitness());
+        assert_relative_eq!(stats.diversity(), expected.diversity());
+    }
+
+    #[test]
+    fn test_evolve_with_replacement() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_replacement(MuPlusLambdaReplacement::new(6));
+
+        let mut population = vec![
+            individual(&[0.5, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 2.0]),
+            individual(&[1.0, 2.0, 1.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        for _ in 0..10 {
+            let fitnesses: Vec<_> =
+                population.iter().map(Individual::fitness).collect();
+            let (evolved, _) = ga.evolve(&mut rng, &population);
+
+            assert_eq!(evolved.len(), population.len());
+
+            // With (μ+λ), every survivor is at least as good as the worst
+            // individual of the previous generation
+            let worst = fitnesses.iter().cloned().fold(f32::MAX, f32::min);
+            assert!(evolved
+                .iter()
+                .all(|individual| individual.fitness() >= worst));
+
+            population = evolved;
+        }
+    }
+
+    #[test]
+    fn test_evolve_with_deferred_evaluation() {
+        let ga = || {
+            GeneticAlgorithm::new(
+                TournamentSelection::new(2, 1.0),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_elitism(1)
+            .with_replacement(SteadyStateReplacement::new(1))
+        };
+
+        evolve_eager_and_deferred(ga);
+    }
+
+    #[test]
+    fn test_one_fifth_rule_with_deferred_evaluation() {
+        let ga = || {
+            GeneticAlgorithm::new(
+                TournamentSelection::new(2, 1.0),
+                UniformCrossover::new(),
+                OneFifthRuleMutation::new(0.5, 0.5, 0.82),
+            )
+        };
+
+        let (eager, deferred) = evolve_eager_and_deferred(ga);
+
+        // Successes are counted once offspring have been evaluated, so the
+        // magnitude doesn't just keep shrinking
+        assert_relative_eq!(
+            deferred.mutation_method.coeff(),
+            eager.mutation_method.coeff()
+        );
+
+        assert!(deferred.mutation_method.coeff() > 0.5 * 0.82f32.powi(9));
+    }
+
+    #[test]
+    fn test_one_fifth_rule_with_foreign_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            UniformCrossover::new(),
+            OneFifthRuleMutation::new(0.5, 0.5, 0.82),
+        );
+
+        let population = vec![
+            individual(&[0.5, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 2.0]),
+            individual(&[1.0, 2.0, 1.0]),
+        ];
+
+        ga.evolve(&mut rng, &population);
+
+        // Population of the same size, but not the one we've got back -
+        // there are no offspring to learn from
+        let (mut evolved, _) = ga.evolve(&mut rng, &population);
+
+        assert_relative_eq!(ga.mutation_method.coeff(), 0.5);
+
+        // Offspring that got replaced (here: by an immigrant) are skipped,
+        // the rest still counts
+        evolved[0] = individual(&[9.0, 9.0, 9.0]);
+
+        ga.evolve(&mut rng, &evolved);
+
+        assert!(ga.mutation_method.coeff() != 0.5);
+    }
+
+    /// Evolves the same population with two copies of an algorithm: one
+    /// with offspring evaluated right away, and one with offspring
+    /// evaluated only after they've been returned (e.g. by running a
+    /// simulation); checks both runs go exactly the same way.
+    fn evolve_eager_and_deferred<M, R>(
+        ga: impl Fn() -> Algorithm<M, R>,
+    ) -> (Algorithm<M, R>, Algorithm<M, R>)
+    where
+        M: MutationMethod,
+        R: ReplacementMethod,
+    {
+        let genes = [[0.5, 0.0, 0.0], [1.0, 1.0, 2.0], [1.0, 2.0, 1.0]];
+
+        let mut eager: Vec<_> =
+            genes.iter().map(|genes| individual(genes)).collect();
+
+        let mut deferred: Vec<_> =
+            genes.iter().map(|genes| Deferred::new(genes)).collect();
+
+        let (mut eager_ga, mut eager_rng) =
+            (ga(), ChaCha8Rng::from_seed(Default::default()));
+
+        let (mut deferred_ga, mut deferred_rng) =
+            (ga(), ChaCha8Rng::from_seed(Default::default()));
+
+        for _ in 0..10 {
+            eager = eager_ga.evolve(&mut eager_rng, &eager).0;
+            deferred = deferred_ga.evolve(&mut deferred_rng, &deferred).0;
+
+            for individual in &mut deferred {
+                individual.evaluate();
+            }
+
+            assert_eq!(deferred.len(), genes.len());
+            assert_eq!(chromosomes(&eager), chromosomes(&deferred));
+        }
+
+        (eager_ga, deferred_ga)
+    }
+
+    #[test]
+    fn test_evolve_adapts_mutation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            AnnealingMutation::new(0.5, 1.0, 0.5),
+        );
+
+        let mut population = vec![
+            individual(&[0.5, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 2.0]),
+            individual(&[1.0, 2.0, 1.0]),
+        ];
+
+        for _ in 0..3 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        // Adaptation happens right before breeding, so the magnitude is the
+        // one the last generation got bred with
+        assert_eq!(ga.generation(), 3);
+        assert_relative_eq!(ga.mutation_method.coeff(), 0.25);
+    }
+
+    #[test]
+    fn test_evolve_inherits_step_sizes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            SelfAdaptiveMutation::new(0.1),
+        );
+
+        let mut population = vec![
+            individual(&[0.5, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 2.0]),
+            individual(&[1.0, 2.0, 1.0]),
+        ];
+
+        for _ in 0..5 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        // Children inherit step sizes from their parents instead of starting
+        // over from the initial one
+        assert!(population.iter().all(|individual| {
+            let step_sizes = individual.chromosome().step_sizes();
+
+            step_sizes.len() == 3
+                && step_sizes.iter().all(|&step_size| step_size != 0.1)
+        }));
+    }
+
+    #[test]
+    fn test_evolve_with_bounds() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let bounds =
+            GeneBounds::uniform(-1.0, 1.0).with_policy(BoundaryPolicy::Reflect);
+
+        let mut ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            BlendCrossover::new(0.5),
+            GaussianMutation::new(1.0, 5.0),
+        )
+        .with_bounds(bounds.clone());
+
+        let mut population = vec![
+            individual(&[0.5, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 0.5]),
+            individual(&[1.0, 0.5, 1.0]),
+        ];
+
+        for _ in 0..10 {
+            population = ga.evolve(&mut rng, &population).0;
+
+            assert!(population
+                .iter()
+                .all(|individual| bounds.contains(individual.chromosome())));
+        }
+    }
+
+    #[test]
+    fn test_evolve_binary_chromosomes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            UniformCrossover::new(),
+            BitFlipMutation::new(0.05),
+        );
+
+        // Fitness is the number of set bits, so evolution should drive
+        // chromosomes towards all ones
+        let mut population: Vec<TestIndividual<bool>> = (0..20)
+            .map(|_| {
+                let genes = (0..20).map(|_| rng.gen_bool(0.5)).collect();
+
+                TestIndividual::from_chromosome(genes)
+            })
+            .collect();
+
+        let initial = Statistics::new(&population);
+
+        for _ in 0..20 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        let evolved = Statistics::new(&population);
+
+        assert!(evolved.mean_fitness() > initial.mean_fitness() + 5.0);
+    }
+
+    #[test]
+    fn test_evolve_permutation_chromosomes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            PartiallyMappedCrossover::new(),
+            InversionMutation::new(0.5),
+        );
+
+        let mut population: Vec<TestIndividual<usize>> = (0..10)
+            .map(|_| {
+                let mut genes: Vec<_> = (0..10).collect();
+                genes.shuffle(&mut rng);
+
+                TestIndividual::from_chromosome(genes.into_iter().collect())
+            })
+            .collect();
+
+        for _ in 0..10 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        assert!(population.iter().all(|individual| {
+            let mut genes = individual.chromosome().as_slice().to_vec();
+            genes.sort_unstable();
+
+            genes == (0..10).collect::<Vec<_>>()
+        }));
+    }
+
+    #[test]
+    fn test_evolve_with_niching() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        // Clearing with a single niche spanning the whole population leaves
+        // only the best individual with non-zero fitness
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_niching(Clearing::new(
+            EuclideanDistance::new(),
+            100.0,
+            1,
+        ));
+
+        let population = vec![
+            individual(&[1.0, 1.0, 2.0]),
+            individual(&[1.0, 2.0, 4.0]),
+            individual(&[1.0, 2.0, 1.0]),
+        ];
+
+        let ranked = ga.rank(&population);
+        let parents =
+            ga.select_parents(&mut rng, &population, &ranked).unwrap();
+
+        assert_eq!(parents.len(), 6);
+        assert!(parents.iter().all(|parent| *parent == &population[1]));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_evolve_with_hall_of_fame() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let mut hall_of_fame = HallOfFame::new(3);
+
+        let mut population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 2.0]),
+            individual(&[1.0, 2.0, 1.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        let mut best = f32::MIN;
+
+        for _ in 0..10 {
+            best = best.max(best_fitness(&population));
+
+            population = ga
+                .evolve_with_hall_of_fame(
+                    &mut rng,
+                    &population,
+                    &mut hall_of_fame,
+                )
+                .0;
+        }
+
+        assert_eq!(hall_of_fame.len(), 3);
+        assert_eq!(hall_of_fame.best().unwrap().fitness(), best);
+    }
+
+    #[test]
+    fn test_try_evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            individual(&[-1.0, -1.0, 0.0]),
+            individual(&[-1.0, 2.0, -4.0]),
+            individual(&[1.0, -2.0, 1.0]),
+        ];
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        assert_eq!(
+            ga.try_evolve(&mut rng, &population).map(|_| ()),
+            Err(SelectionError::NegativeFitness { fitness: -2.0 })
+        );
+
+        assert_eq!(
+            ga.try_evolve(&mut rng, &Vec::<TestIndividual>::new()).map(|_| ()),
+            Err(SelectionError::EmptyPopulation)
+        );
+
+        let mut elitist = GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_elitism(4);
+
+        assert_eq!(
+            elitist.try_evolve(&mut rng, &population).map(|_| ()),
+            Err(SelectionError::TooManyElites {
+                elitism: 4,
+                population: 3
+            })
+        );
+
+        // Windowing shifts fitness, so roulette can cope with it
+        let mut ga = ga.with_scaling(Windowing::new());
+        let (evolved, _) = ga.try_evolve(&mut rng, &population).unwrap();
+
+        assert_eq!(evolved.len(), population.len());
+        assert_eq!(ga.generation(), 1);
+    }
+}
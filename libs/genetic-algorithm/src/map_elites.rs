@@ -0,0 +1,252 @@
+use crate::*;
+
+/// Quality-diversity archive (MAP-Elites): a grid over the behaviour space,
+/// keeping the fittest individual (the elite) of each cell.
+///
+/// Instead of a single best solution, the grid ends up with the best
+/// solution for each *kind* of behaviour, e.g. the best slow bird, the best
+/// fast bird, the best bird that keeps turning and so on.
+///
+/// Individuals are cloned (rather than re-created from their chromosomes),
+/// so that fitness computed outside of `Individual::from_chromosome` gets
+/// preserved.
+#[derive(Clone, Debug)]
+pub struct MapElites<I> {
+    /// Range of each behaviour dimension; behaviours outside of it fall
+    /// into the boundary cells.
+    ranges: Vec<(f32, f32)>,
+    /// Number of cells along each dimension.
+    bins: usize,
+    /// Elites, with cells stored dimension after dimension (the first
+    /// dimension varying the slowest).
+    cells: Vec<Option<I>>,
+}
+
+impl<I> MapElites<I> {
+    pub fn new(ranges: Vec<(f32, f32)>, bins: usize) -> Self {
+        assert!(!ranges.is_empty());
+        assert!(ranges.iter().all(|(min, max)| min < max));
+        assert!(bins > 0);
+
+        let cells = (0..bins.pow(ranges.len() as u32)).map(|_| None).collect();
+
+        Self { ranges, bins, cells }
+    }
+
+    /// Returns coordinates of the cell given behaviour falls into.
+    pub fn cell(&self, behaviour: &[f32]) -> Vec<usize> {
+        assert_eq!(behaviour.len(), self.ranges.len());
+
+        behaviour
+            .iter()
+            .zip(&self.ranges)
+            .map(|(&value, &(min, max))| {
+                let bin = (value - min) / (max - min) * self.bins as f32;
+
+                (bin.max(0.0) as usize).min(self.bins - 1)
+            })
+            .collect()
+    }
+
+    /// Returns elite of the cell at given coordinates, if any.
+    pub fn get(&self, cell: &[usize]) -> Option<&I> {
+        self.cells[self.index(cell)].as_ref()
+    }
+
+    /// Returns all the elites, in cell order.
+    pub fn elites(&self) -> impl Iterator<Item = &I> {
+        self.cells.iter().flatten()
+    }
+
+    /// Returns number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.elites().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total number of cells.
+    pub fn capacity(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns fraction of occupied cells, from 0.0 to 1.0.
+    pub fn coverage(&self) -> f32 {
+        self.len() as f32 / self.capacity() as f32
+    }
+
+    /// Returns the fittest of all elites.
+    pub fn best<G>(&self) -> Option<&I>
+    where
+        I: Individual<G>,
+    {
+        self.elites().max_by(|a, b| a.fitness().total_cmp(&b.fitness()))
+    }
+
+    /// Returns the sum of the elites' fitnesses (the "QD score"), which
+    /// grows both with quality and with diversity of the elites.
+    pub fn qd_score<G>(&self) -> f32
+    where
+        I: Individual<G>,
+    {
+        self.elites().map(Individual::fitness).sum()
+    }
+
+    /// Makes given individual the elite of its cell, if the cell is empty
+    /// or holds a less fit individual; returns whether that happened.
+    pub fn insert<G>(&mut self, individual: &I) -> bool
+    where
+        I: BehaviouralIndividual<G> + Clone,
+    {
+        let idx = self.index(&self.cell(&individual.behaviour()));
+
+        let improves = self.cells[idx]
+            .as_ref()
+            .map_or(true, |elite| individual.fitness() > elite.fitness());
+
+        if improves {
+            self.cells[idx] = Some(individual.clone());
+        }
+
+        improves
+    }
+
+    /// Inserts all individuals of `population`; returns how many of them
+    /// became elites.
+    pub fn update<G>(&mut self, population: &[I]) -> usize
+    where
+        I: BehaviouralIndividual<G> + Clone,
+    {
+        population.iter().filter(|individual| self.insert(*individual)).count()
+    }
+
+    /// Returns `count` elites picked at random (with repetitions), e.g. to
+    /// serve as the next population to evolve; returns nothing when the
+    /// grid is empty.
+    pub fn sample(&self, rng: &mut dyn RngCore, count: usize) -> Vec<I>
+    where
+        I: Clone,
+    {
+        let elites: Vec<_> = self.elites().collect();
+
+        if elites.is_empty() {
+            return Vec::new();
+        }
+
+        (0..count).map(|_| (*elites.choose(rng).unwrap()).clone()).collect()
+    }
+
+    fn index(&self, cell: &[usize]) -> usize {
+        assert_eq!(cell.len(), self.ranges.len());
+
+        cell.iter().fold(0, |idx, &coordinate| {
+            assert!(coordinate < self.bins);
+
+            idx * self.bins + coordinate
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Individual whose behaviour is given by its first two genes and
+    /// fitness by the third one.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Bird {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for Bird {
+        fn from_chromosome(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            self.chromosome[2]
+        }
+    }
+
+    impl BehaviouralIndividual for Bird {
+        fn behaviour(&self) -> Vec<f32> {
+            vec![self.chromosome[0], self.chromosome[1]]
+        }
+    }
+
+    fn bird(speed: f32, turning: f32, fitness: f32) -> Bird {
+        Bird::from_chromosome(
+            vec![speed, turning, fitness].into_iter().collect(),
+        )
+    }
+
+    fn grid() -> MapElites<Bird> {
+        MapElites::new(vec![(0.0, 1.0), (0.0, 2.0)], 4)
+    }
+
+    #[test]
+    fn test_cell() {
+        let grid = grid();
+
+        assert_eq!(grid.cell(&[0.0, 0.0]), vec![0, 0]);
+        assert_eq!(grid.cell(&[0.3, 1.9]), vec![1, 3]);
+        assert_eq!(grid.cell(&[1.0, 1.0]), vec![3, 2]);
+
+        // Out of range
+        assert_eq!(grid.cell(&[-1.0, 5.0]), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_keeps_the_fittest_per_cell() {
+        let mut grid = grid();
+
+        let inserted = grid.update(&[
+            bird(0.1, 0.1, 1.0),
+            bird(0.2, 0.2, 3.0),
+            bird(0.9, 1.9, 2.0),
+            bird(0.15, 0.3, 2.0),
+        ]);
+
+        assert_eq!(inserted, 3);
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.capacity(), 16);
+        assert_relative_eq!(grid.coverage(), 0.125);
+        assert_eq!(grid.get(&[0, 0]), Some(&bird(0.2, 0.2, 3.0)));
+        assert_eq!(grid.get(&[3, 3]), Some(&bird(0.9, 1.9, 2.0)));
+        assert_eq!(grid.get(&[1, 1]), None);
+
+        // Worse individual doesn't replace the elite
+        assert!(!grid.insert(&bird(0.0, 0.0, 2.5)));
+        assert!(grid.insert(&bird(0.0, 0.0, 4.0)));
+
+        assert_eq!(grid.best(), Some(&bird(0.0, 0.0, 4.0)));
+        assert_relative_eq!(grid.qd_score(), 6.0);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut grid = grid();
+
+        assert!(grid.is_empty());
+        assert!(grid.sample(&mut rng, 5).is_empty());
+
+        grid.update(&[bird(0.1, 0.1, 1.0), bird(0.9, 1.9, 2.0)]);
+
+        let sample = grid.sample(&mut rng, 5);
+
+        assert_eq!(sample.len(), 5);
+        assert!(sample
+            .iter()
+            .all(|bird| grid.elites().any(|elite| elite == bird)));
+    }
+}
@@ -1,37 +1,757 @@
+use crate::crossover::random_segment;
 use crate::*;
+use rand::seq::index;
+use rand_distr::StandardNormal;
 
-pub trait MutationMethod {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
+pub trait MutationMethod<G = f32> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
+
+    /// Lets the method adjust itself after every generation.
+    ///
+    /// `generation` counts evolved generations, starting from 1, and
+    /// `success_rate` is the fraction of offspring that turned out fitter
+    /// than both of their parents.
+    ///
+    /// [`GeneticAlgorithm`] calls it right before breeding the next
+    /// generation, once offspring of the previous one have been evaluated.
+    fn adapt(&mut self, _generation: usize, _success_rate: f32) {}
 }
 
+/// Adds normally distributed noise to genes; integer genes get rounded
+/// afterwards.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GaussianMutation {
     /// Probability of changing a gene:
     /// - 0.0 = no genes will be touched
     /// - 1.0 = all genes will be touched
     chance: f32,
-    /// Magnitude of that change:
+    /// Standard deviation of that change:
     /// - 0.0 = touched genes will not be modified
-    /// - 3.0 = touched genes will be += or -= by at most 3.0
+    /// - 3.0 = touched genes will be += or -= by less than 3.0 in ~68% of
+    ///   cases
     coeff: f32,
 }
 
 impl GaussianMutation {
     pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!(chance >= 0.0 && chance <= 1.0);
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(coeff >= 0.0);
 
         Self { chance, coeff }
     }
 }
 
-impl MutationMethod for GaussianMutation {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+impl<G: Gene> MutationMethod<G> for GaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
         for gene in child.iter_mut() {
-            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+            if rng.gen_bool(self.chance as f64) {
+                let change = self.coeff * rng.sample::<f32, _>(StandardNormal);
+
+                *gene = G::from_f32(gene.to_f32() + change);
+            }
+        }
+    }
+}
+
+/// Gaussian mutation whose magnitude shrinks exponentially over
+/// generations, moving the search from exploration to fine-tuning.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnnealingMutation {
+    mutation: GaussianMutation,
+    /// Magnitude of the change in the first generation.
+    initial_coeff: f32,
+    /// Factor the magnitude gets multiplied by every generation:
+    /// - 1.0 = magnitude stays the same
+    /// - 0.99 = magnitude halves every ~70 generations
+    decay: f32,
+}
+
+impl AnnealingMutation {
+    pub fn new(chance: f32, coeff: f32, decay: f32) -> Self {
+        assert!(decay > 0.0 && decay <= 1.0);
+
+        Self {
+            mutation: GaussianMutation::new(chance, coeff),
+            initial_coeff: coeff,
+            decay,
+        }
+    }
+
+    pub fn coeff(&self) -> f32 {
+        self.mutation.coeff
+    }
+}
+
+impl MutationMethod for AnnealingMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.mutation.mutate(rng, child);
+    }
+
+    fn adapt(&mut self, generation: usize, _success_rate: f32) {
+        self.mutation.coeff =
+            self.initial_coeff * self.decay.powi(generation as i32);
+    }
+}
+
+/// Gaussian mutation tuned by Rechenberg's 1/5th success rule: when more
+/// than a fifth of offspring outperform their parents, the magnitude grows;
+/// when fewer do, it shrinks.
+///
+/// Success rate is determined once offspring have been evaluated, so it
+/// works also when fitness is computed outside of
+/// `Individual::from_chromosome`.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OneFifthRuleMutation {
+    mutation: GaussianMutation,
+    /// Factor the magnitude gets multiplied (on too few successes) or
+    /// divided (on too many successes) by; usually around 0.82.
+    factor: f32,
+}
+
+impl OneFifthRuleMutation {
+    const TARGET_SUCCESS_RATE: f32 = 0.2;
+
+    pub fn new(chance: f32, coeff: f32, factor: f32) -> Self {
+        assert!(factor > 0.0 && factor < 1.0);
+
+        Self { mutation: GaussianMutation::new(chance, coeff), factor }
+    }
+
+    pub fn coeff(&self) -> f32 {
+        self.mutation.coeff
+    }
+}
+
+impl MutationMethod for OneFifthRuleMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        self.mutation.mutate(rng, child);
+    }
+
+    fn adapt(&mut self, _generation: usize, success_rate: f32) {
+        if success_rate > Self::TARGET_SUCCESS_RATE {
+            self.mutation.coeff /= self.factor;
+        } else if success_rate < Self::TARGET_SUCCESS_RATE {
+            self.mutation.coeff *= self.factor;
+        }
+    }
+}
+
+/// Evolution-strategies style mutation where every chromosome carries its
+/// own per-gene step sizes, which get mutated (log-normally) before being
+/// used to mutate the genes themselves.
+///
+/// Chromosomes without step sizes get them initialized to
+/// `initial_step_size`; offspring inherit averaged step sizes of their
+/// parents.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SelfAdaptiveMutation {
+    initial_step_size: f32,
+    /// Lower bound keeping step sizes from collapsing to zero.
+    min_step_size: f32,
+}
+
+impl SelfAdaptiveMutation {
+    pub fn new(initial_step_size: f32) -> Self {
+        assert!(initial_step_size > 0.0);
+
+        Self { initial_step_size, min_step_size: 1e-5 }
+    }
+
+    pub fn with_min_step_size(mut self, min_step_size: f32) -> Self {
+        assert!(min_step_size > 0.0);
+
+        self.min_step_size = min_step_size;
+        self
+    }
+}
+
+impl MutationMethod for SelfAdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        if child.is_empty() {
+            return;
+        }
+
+        if child.step_sizes().is_empty() {
+            child.set_step_sizes(vec![self.initial_step_size; child.len()]);
+        }
+
+        // Learning rates recommended by Schwefel
+        let len = child.len() as f32;
+        let tau_global = 1.0 / (2.0 * len).sqrt();
+        let tau_local = 1.0 / (2.0 * len.sqrt()).sqrt();
+        let global = tau_global * rng.sample::<f32, _>(StandardNormal);
+
+        for step_size in child.step_sizes_mut() {
+            let local = tau_local * rng.sample::<f32, _>(StandardNormal);
+
+            *step_size =
+                (*step_size * (global + local).exp()).max(self.min_step_size);
+        }
+
+        let step_sizes = child.step_sizes().to_vec();
+
+        for (gene, step_size) in child.iter_mut().zip(step_sizes) {
+            *gene += step_size * rng.sample::<f32, _>(StandardNormal);
+        }
+    }
+}
+
+/// Polynomial mutation for real-coded genes.
+///
+/// Perturbs genes by amounts drawn from a polynomial distribution centered
+/// at zero. With bounds, perturbations are scaled by the width of each
+/// gene's range and never push a gene outside of it; without bounds, genes
+/// are changed by at most 1.0.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolynomialMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+    /// Distribution index:
+    /// - 0.0 = large changes are as likely as small ones
+    /// - 20.0 = changes are mostly tiny
+    eta: f32,
+    /// Optional range of each gene.
+    bounds: Option<GeneBounds>,
+}
+
+impl PolynomialMutation {
+    pub fn new(chance: f32, eta: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(eta >= 0.0);
+
+        Self { chance, eta, bounds: None }
+    }
+
+    pub fn with_bounds(mut self, bounds: GeneBounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    fn perturb(
+        &self,
+        rng: &mut dyn RngCore,
+        gene: f32,
+        bounds: Option<(f32, f32)>,
+    ) -> f32 {
+        let u = rng.gen::<f32>();
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        match bounds {
+            None => {
+                let delta = if u < 0.5 {
+                    (2.0 * u).powf(exponent) - 1.0
+                } else {
+                    1.0 - (2.0 * (1.0 - u)).powf(exponent)
+                };
+
+                gene + delta
+            }
+
+            Some((min, max)) if max > min => {
+                let range = max - min;
+                let gene = gene.clamp(min, max);
+
+                let delta = if u < 0.5 {
+                    let xy = 1.0 - (gene - min) / range;
+                    let val =
+                        2.0 * u + (1.0 - 2.0 * u) * xy.powf(self.eta + 1.0);
+
+                    val.powf(exponent) - 1.0
+                } else {
+                    let xy = 1.0 - (max - gene) / range;
+                    let val = 2.0 * (1.0 - u)
+                        + 2.0 * (u - 0.5) * xy.powf(self.eta + 1.0);
 
+                    1.0 - val.powf(exponent)
+                };
+
+                (gene + delta * range).clamp(min, max)
+            }
+
+            // Degenerate range allows just a single value
+            Some((min, _)) => min,
+        }
+    }
+}
+
+impl MutationMethod for PolynomialMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        if let Some(bounds) = &self.bounds {
+            // Genes might've been out of bounds from the get-go
+            bounds.apply(child);
+        }
+
+        for (idx, gene) in child.iter_mut().enumerate() {
             if rng.gen_bool(self.chance as f64) {
-                *gene += sign * self.coeff * rng.gen::<f32>();
+                let bounds =
+                    self.bounds.as_ref().map(|bounds| bounds.range(idx));
+
+                *gene = self.perturb(rng, *gene, bounds);
             }
         }
     }
 }
+
+/// Flips each bit of a binary chromosome with given probability.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitFlipMutation {
+    /// Probability of flipping a bit:
+    /// - 0.0 = no bits will be touched
+    /// - 1.0 = all bits will be flipped
+    chance: f32,
+}
+
+impl BitFlipMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl MutationMethod<bool> for BitFlipMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<bool>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as f64) {
+                *gene = !*gene;
+            }
+        }
+    }
+}
+
+/// Swaps two random genes; keeps permutations valid.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapMutation {
+    /// Probability of mutating a chromosome:
+    /// - 0.0 = no chromosomes will be touched
+    /// - 1.0 = all chromosomes will be touched
+    chance: f32,
+}
+
+impl SwapMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl<G> MutationMethod<G> for SwapMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if child.len() < 2 || !rng.gen_bool(self.chance as f64) {
+            return;
+        }
+
+        let genes = index::sample(rng, child.len(), 2);
+
+        child.as_mut_slice().swap(genes.index(0), genes.index(1));
+    }
+}
+
+/// Reverses the order of genes within a random segment; keeps permutations
+/// valid.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InversionMutation {
+    /// Probability of mutating a chromosome:
+    /// - 0.0 = no chromosomes will be touched
+    /// - 1.0 = all chromosomes will be touched
+    chance: f32,
+}
+
+impl InversionMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl<G> MutationMethod<G> for InversionMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if !rng.gen_bool(self.chance as f64) {
+            return;
+        }
+
+        let segment = random_segment(rng, child.len());
+
+        child.as_mut_slice()[segment].reverse();
+    }
+}
+
+/// Shuffles genes within a random segment; keeps permutations valid.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScrambleMutation {
+    /// Probability of mutating a chromosome:
+    /// - 0.0 = no chromosomes will be touched
+    /// - 1.0 = all chromosomes will be touched
+    chance: f32,
+}
+
+impl ScrambleMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl<G> MutationMethod<G> for ScrambleMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if !rng.gen_bool(self.chance as f64) {
+            return;
+        }
+
+        let segment = random_segment(rng, child.len());
+
+        child.as_mut_slice()[segment].shuffle(rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn genes() -> Vec<f32> {
+        vec![1.0, 2.0, 3.0, 4.0, 5.0]
+    }
+
+    fn mutate<M: MutationMethod>(method: &M) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child: Chromosome = genes().into_iter().collect();
+
+        method.mutate(&mut rng, &mut child);
+        child.into_iter().collect()
+    }
+
+    mod gaussian {
+        use super::*;
+
+        #[test]
+        fn given_zero_chance() {
+            let actual = mutate(&GaussianMutation::new(0.0, 0.5));
+            let expected = genes();
+
+            assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn given_zero_coeff() {
+            let actual = mutate(&GaussianMutation::new(0.5, 0.0));
+
+            let expected = genes();
+
+            assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn given_nonzero_chance_and_coeff() {
+            let actual = mutate(&GaussianMutation::new(0.5, 0.5));
+            let expected = vec![1.0, 2.0, 2.0324764, 3.467692, 4.4987187];
+
+            assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn changes_are_normally_distributed() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = vec![0.0; 10_000].into_iter().collect();
+
+            GaussianMutation::new(1.0, 2.0).mutate(&mut rng, &mut child);
+
+            let len = child.len() as f32;
+            let mean = child.iter().sum::<f32>() / len;
+            let std_dev =
+                (child.iter().map(|g| (g - mean).powi(2)).sum::<f32>() / len)
+                    .sqrt();
+
+            // ~68% of changes lie within one standard deviation
+            let within_std_dev =
+                child.iter().filter(|g| g.abs() < 2.0).count() as f32 / len;
+
+            assert_relative_eq!(mean, 0.0, epsilon = 0.05);
+            assert_relative_eq!(std_dev, 2.0, epsilon = 0.05);
+            assert_relative_eq!(within_std_dev, 0.68, epsilon = 0.01);
+        }
+    }
+
+    mod annealing {
+        use super::*;
+
+        #[test]
+        fn coeff_decays() {
+            let mut method = AnnealingMutation::new(0.5, 2.0, 0.5);
+
+            assert_relative_eq!(method.coeff(), 2.0);
+
+            method.adapt(1, 0.0);
+            assert_relative_eq!(method.coeff(), 1.0);
+
+            method.adapt(3, 0.0);
+            assert_relative_eq!(method.coeff(), 0.25);
+        }
+
+        #[test]
+        fn mutates_like_gaussian() {
+            let mut method = AnnealingMutation::new(0.5, 1.0, 0.5);
+            method.adapt(1, 0.0);
+
+            let actual = mutate(&method);
+            let expected = mutate(&GaussianMutation::new(0.5, 0.5));
+
+            assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+    }
+
+    mod one_fifth_rule {
+        use super::*;
+
+        #[test]
+        fn given_many_successes() {
+            let mut method = OneFifthRuleMutation::new(0.5, 1.0, 0.5);
+            method.adapt(1, 0.5);
+
+            assert_relative_eq!(method.coeff(), 2.0);
+        }
+
+        #[test]
+        fn given_few_successes() {
+            let mut method = OneFifthRuleMutation::new(0.5, 1.0, 0.5);
+            method.adapt(1, 0.1);
+
+            assert_relative_eq!(method.coeff(), 0.5);
+        }
+
+        #[test]
+        fn given_exactly_one_fifth_successes() {
+            let mut method = OneFifthRuleMutation::new(0.5, 1.0, 0.5);
+            method.adapt(1, 0.2);
+
+            assert_relative_eq!(method.coeff(), 1.0);
+        }
+    }
+
+    mod self_adaptive {
+        use super::*;
+
+        #[test]
+        fn initializes_step_sizes() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = genes().into_iter().collect();
+
+            SelfAdaptiveMutation::new(0.1).mutate(&mut rng, &mut child);
+
+            let step_sizes = child.step_sizes().to_vec();
+            let expected = vec![
+                0.18726282,
+                0.087808274,
+                0.061917882,
+                0.11252486,
+                0.093448505,
+            ];
+
+            assert_relative_eq!(step_sizes.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn mutates_existing_step_sizes() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = genes().into_iter().collect();
+
+            child.set_step_sizes(vec![1e-9, 1.0, 1.0, 1.0, 1.0]);
+            SelfAdaptiveMutation::new(0.1)
+                .with_min_step_size(0.01)
+                .mutate(&mut rng, &mut child);
+
+            let genes: Vec<_> = child.iter().cloned().collect();
+            let expected_genes =
+                vec![0.97851026, 1.1196675, 2.7866833, 4.4428444, 5.3411913];
+            let step_sizes = child.step_sizes().to_vec();
+            // The first step size is kept from collapsing below the minimum
+            let expected_step_sizes =
+                vec![0.01, 0.8780827, 0.61917883, 1.1252486, 0.934485];
+
+            assert_relative_eq!(genes.as_slice(), expected_genes.as_slice());
+            assert_relative_eq!(
+                step_sizes.as_slice(),
+                expected_step_sizes.as_slice()
+            );
+        }
+    }
+
+    mod polynomial {
+        use super::*;
+
+        fn mutate(method: PolynomialMutation) -> Vec<f32> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome = original().into_iter().collect();
+
+            method.mutate(&mut rng, &mut child);
+            child.into_iter().collect()
+        }
+
+        fn original() -> Vec<f32> {
+            (1..=100).map(|n| n as f32 / 100.0).collect()
+        }
+
+        #[test]
+        #[allow(clippy::float_cmp)]
+        fn given_zero_chance() {
+            assert_eq!(mutate(PolynomialMutation::new(0.0, 20.0)), original());
+        }
+
+        #[test]
+        fn given_nonzero_chance() {
+            let actual = mutate(PolynomialMutation::new(0.5, 20.0));
+
+            // Number of genes that got changed
+            let changed = actual
+                .iter()
+                .zip(original())
+                .filter(|(a, o)| **a != *o)
+                .count();
+
+            // Largest change of a gene
+            let max_change = actual
+                .iter()
+                .zip(original())
+                .map(|(a, o)| (a - o).abs())
+                .fold(0.0, f32::max);
+
+            assert_eq!(changed, 43);
+            assert!(max_change < 1.0);
+        }
+
+        #[test]
+        fn given_bounds() {
+            let bounds = GeneBounds::uniform(0.0, 0.5);
+            let actual =
+                mutate(PolynomialMutation::new(1.0, 1.0).with_bounds(bounds));
+
+            let within_bounds =
+                actual.iter().all(|&gene| (0.0..=0.5).contains(&gene));
+
+            // Number of genes that got changed
+            let changed = actual
+                .iter()
+                .zip(original())
+                .filter(|(a, o)| **a != *o)
+                .count();
+
+            assert!(within_bounds);
+            assert_eq!(changed, 99);
+        }
+    }
+
+    mod bit_flip {
+        use super::*;
+
+        fn mutate(chance: f32) -> Vec<bool> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome<bool> =
+                vec![false; 100].into_iter().collect();
+
+            BitFlipMutation::new(chance).mutate(&mut rng, &mut child);
+            child.into_iter().collect()
+        }
+
+        #[test]
+        fn given_zero_chance() {
+            assert!(mutate(0.0).iter().all(|&gene| !gene));
+        }
+
+        #[test]
+        fn given_max_chance() {
+            assert!(mutate(1.0).iter().all(|&gene| gene));
+        }
+
+        #[test]
+        fn given_fractional_chance() {
+            let flipped = mutate(0.25).iter().filter(|&&gene| gene).count();
+
+            assert_eq!(flipped, 25);
+        }
+    }
+
+    mod permutation {
+        use super::*;
+
+        fn mutate<M: MutationMethod<usize>>(method: M) -> Vec<usize> {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome<usize> = (0..10).collect();
+
+            method.mutate(&mut rng, &mut child);
+            child.into_iter().collect()
+        }
+
+        fn assert_permutation(genes: &[usize]) {
+            let mut genes = genes.to_vec();
+
+            genes.sort_unstable();
+            assert_eq!(genes, (0..10).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn given_zero_chance() {
+            let expected: Vec<_> = (0..10).collect();
+
+            assert_eq!(mutate(SwapMutation::new(0.0)), expected);
+            assert_eq!(mutate(InversionMutation::new(0.0)), expected);
+            assert_eq!(mutate(ScrambleMutation::new(0.0)), expected);
+        }
+
+        #[test]
+        fn swap() {
+            let actual = mutate(SwapMutation::new(1.0));
+
+            assert_permutation(&actual);
+            assert_eq!(actual, vec![0, 1, 2, 3, 4, 5, 6, 9, 8, 7]);
+        }
+
+        #[test]
+        fn inversion() {
+            let actual = mutate(InversionMutation::new(1.0));
+
+            assert_permutation(&actual);
+            assert_eq!(actual, vec![0, 1, 8, 7, 6, 5, 4, 3, 2, 9]);
+        }
+
+        #[test]
+        fn scramble() {
+            let actual = mutate(ScrambleMutation::new(1.0));
+
+            assert_permutation(&actual);
+            assert_eq!(actual, vec![0, 1, 6, 7, 8, 3, 4, 2, 5, 9]);
+        }
+    }
+
+    mod integer {
+        use super::*;
+
+        #[test]
+        fn gaussian_rounds_genes() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child: Chromosome<i32> = vec![0; 5].into_iter().collect();
+
+            GaussianMutation::new(1.0, 3.0).mutate(&mut rng, &mut child);
+
+            assert_eq!(
+                child.into_iter().collect::<Vec<_>>(),
+                vec![4, 1, -4, -6, -2]
+            );
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use crate::*;
+
+/// Adjusts fitness of individuals depending on how crowded their region of
+/// the search space is, so that a single strategy can't take over the whole
+/// population.
+///
+/// Adjusted fitness is seen only by the selection method; elitism, the
+/// replacement method and statistics still use [`Individual::fitness`].
+pub trait NichingMethod<G = f32> {
+    /// Returns adjusted fitness of each individual, in population order.
+    fn adjust<I: Individual<G>>(&self, population: &[I]) -> Vec<f32>;
+}
+
+/// Leaves fitness as it is.
+#[derive(Clone, Debug, Default)]
+pub struct NoNiching;
+
+impl NoNiching {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> NichingMethod<G> for NoNiching {
+    fn adjust<I: Individual<G>>(&self, population: &[I]) -> Vec<f32> {
+        population.iter().map(Individual::fitness).collect()
+    }
+}
+
+/// Fitness sharing: individuals share their fitness with everyone within
+/// `radius`, so crowded niches become less attractive than sparse ones.
+///
+/// Each individual's fitness gets divided by its niche count, i.e. the sum
+/// of `1 - (distance / radius)^alpha` over all individuals closer than
+/// `radius` (including itself).
+#[derive(Clone, Debug)]
+pub struct FitnessSharing<D> {
+    metric: D,
+    /// Distance below which individuals share fitness.
+    radius: f32,
+    /// Shape of the sharing function:
+    /// - 1.0 = sharing decreases linearly with distance
+    /// - larger = only really close individuals share much
+    alpha: f32,
+}
+
+impl<D> FitnessSharing<D> {
+    pub fn new(metric: D, radius: f32) -> Self {
+        assert!(radius > 0.0);
+
+        Self { metric, radius, alpha: 1.0 }
+    }
+
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        assert!(alpha > 0.0);
+
+        self.alpha = alpha;
+        self
+    }
+}
+
+impl<G, D> NichingMethod<G> for FitnessSharing<D>
+where
+    D: DistanceMetric<G>,
+{
+    fn adjust<I: Individual<G>>(&self, population: &[I]) -> Vec<f32> {
+        population
+            .iter()
+            .map(|a| {
+                let niche_count: f32 = population
+                    .iter()
+                    .map(|b| {
+                        let distance = self
+                            .metric
+                            .distance(a.chromosome(), b.chromosome());
+
+                        if distance < self.radius {
+                            1.0 - (distance / self.radius).powf(self.alpha)
+                        } else {
+                            0.0
+                        }
+                    })
+                    .sum();
+
+                a.fitness() / niche_count
+            })
+            .collect()
+    }
+}
+
+/// Clearing: only the `capacity` fittest individuals of each niche keep
+/// their fitness, everyone else within `radius` of them gets zero.
+///
+/// Expects non-negative fitness.
+#[derive(Clone, Debug)]
+pub struct Clearing<D> {
+    metric: D,
+    /// Radius of a niche.
+    radius: f32,
+    /// Number of individuals allowed to keep their fitness in each niche:
+    /// - 1 = only the best individual of each niche survives clearing
+    capacity: usize,
+}
+
+impl<D> Clearing<D> {
+    pub fn new(metric: D, radius: f32, capacity: usize) -> Self {
+        assert!(radius > 0.0);
+        assert!(capacity > 0);
+
+        Self { metric, radius, capacity }
+    }
+}
+
+impl<G, D> NichingMethod<G> for Clearing<D>
+where
+    D: DistanceMetric<G>,
+{
+    fn adjust<I: Individual<G>>(&self, population: &[I]) -> Vec<f32> {
+        let mut fitnesses: Vec<_> =
+            population.iter().map(Individual::fitness).collect();
+
+        let mut order: Vec<_> = (0..population.len()).collect();
+        order.sort_by(|&a, &b| fitnesses[b].total_cmp(&fitnesses[a]));
+
+        for (pos, &winner) in order.iter().enumerate() {
+            if fitnesses[winner] <= 0.0 {
+                continue;
+            }
+
+            let mut winners = 1;
+
+            for &other in &order[pos + 1..] {
+                if fitnesses[other] <= 0.0 {
+                    continue;
+                }
+
+                let distance = self.metric.distance(
+                    population[winner].chromosome(),
+                    population[other].chromosome(),
+                );
+
+                if distance < self.radius {
+                    if winners < self.capacity {
+                        winners += 1;
+                    } else {
+                        fitnesses[other] = 0.0;
+                    }
+                }
+            }
+        }
+
+        fitnesses
+    }
+}
+
+/// Explicit speciation: individuals closer than `threshold` to a species'
+/// representative (its first member) belong to that species, and share
+/// fitness with all of its members equally.
+#[derive(Clone, Debug)]
+pub struct Speciation<D> {
+    metric: D,
+    /// Compatibility threshold:
+    /// - smaller = more, smaller species
+    /// - larger = fewer, larger species
+    threshold: f32,
+}
+
+impl<D> Speciation<D> {
+    pub fn new(metric: D, threshold: f32) -> Self {
+        assert!(threshold > 0.0);
+
+        Self { metric, threshold }
+    }
+
+    /// Splits population into species; species contain indices into
+    /// `population`, in the order their representatives appear in it.
+    pub fn species<G, I>(&self, population: &[I]) -> Vec<Vec<usize>>
+    where
+        D: DistanceMetric<G>,
+        I: Individual<G>,
+    {
+        let mut species: Vec<Vec<usize>> = Vec::new();
+
+        for (idx, individual) in population.iter().enumerate() {
+            let compatible = species.iter_mut().find(|members| {
+                let representative = population[members[0]].chromosome();
+
+                self.metric.distance(representative, individual.chromosome())
+                    < self.threshold
+            });
+
+            match compatible {
+                Some(members) => members.push(idx),
+                None => species.push(vec![idx]),
+            }
+        }
+
+        species
+    }
+}
+
+impl<G, D> NichingMethod<G> for Speciation<D>
+where
+    D: DistanceMetric<G>,
+{
+    fn adjust<I: Individual<G>>(&self, population: &[I]) -> Vec<f32> {
+        let mut fitnesses: Vec<_> =
+            population.iter().map(Individual::fitness).collect();
+
+        for members in self.species(population) {
+            let size = members.len() as f32;
+
+            for idx in members {
+                fitnesses[idx] /= size;
+            }
+        }
+
+        fitnesses
+    }
+}
+
+/// Individual whose fitness got adjusted by a niching method; passed to
+/// selection methods in place of the original individual.
+pub(crate) struct Adjusted<'a, I> {
+    pub(crate) individual: &'a I,
+    fitness: f32,
+}
+
+impl<'a, I> Adjusted<'a, I> {
+    pub(crate) fn wrap(population: &'a [I], fitnesses: Vec<f32>) -> Vec<Self> {
+        population
+            .iter()
+            .zip(fitnesses)
+            .map(|(individual, fitness)| Self { individual, fitness })
+            .collect()
+    }
+}
+
+impl<'a, G, I> Individual<G> for Adjusted<'a, I>
+where
+    I: Individual<G>,
+{
+    fn from_chromosome(_chromosome: Chromosome<G>) -> Self {
+        unreachable!("adjusted individuals only ever get selected")
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        self.individual.chromosome()
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::from_chromosome(genes.iter().cloned().collect())
+    }
+
+    /// Two crowded individuals near 1.0 and a lonely one at 10.0; fitness
+    /// equals the only gene.
+    fn population() -> Vec<TestIndividual> {
+        vec![individual(&[1.0]), individual(&[1.5]), individual(&[10.0])]
+    }
+
+    #[test]
+    fn test_no_niching() {
+        let actual = NoNiching::new().adjust(&population());
+        let expected = vec![1.0, 1.5, 10.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_fitness_sharing() {
+        let method = FitnessSharing::new(EuclideanDistance::new(), 2.0);
+
+        let actual = method.adjust(&population());
+        let expected = vec![1.0 / 1.75, 1.5 / 1.75, 10.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_fitness_sharing_with_alpha() {
+        let method =
+            FitnessSharing::new(EuclideanDistance::new(), 2.0).with_alpha(2.0);
+
+        let actual = method.adjust(&population());
+        let expected = vec![1.0 / 1.9375, 1.5 / 1.9375, 10.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_clearing() {
+        let population = vec![
+            individual(&[1.0]),
+            individual(&[1.5]),
+            individual(&[2.0]),
+            individual(&[10.0]),
+        ];
+
+        let actual =
+            Clearing::new(EuclideanDistance::new(), 2.0, 1).adjust(&population);
+        let expected = vec![0.0, 0.0, 2.0, 10.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+
+        let actual =
+            Clearing::new(EuclideanDistance::new(), 2.0, 2).adjust(&population);
+        let expected = vec![0.0, 1.5, 2.0, 10.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_speciation() {
+        let method = Speciation::new(EuclideanDistance::new(), 1.0);
+
+        let population = vec![
+            individual(&[1.0]),
+            individual(&[10.0]),
+            individual(&[1.5]),
+            individual(&[1.9]),
+            individual(&[2.2]),
+        ];
+
+        // [2.2] is close to [1.9], but not to the species' representative
+        assert_eq!(
+            method.species(&population),
+            vec![vec![0, 2, 3], vec![1], vec![4]]
+        );
+
+        let actual = method.adjust(&population);
+        let expected = vec![1.0 / 3.0, 10.0, 1.5 / 3.0, 1.9 / 3.0, 2.2];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+}
@@ -0,0 +1,203 @@
+use crate::*;
+
+/// Individual whose behaviour can be summarized with a few numbers, e.g.
+/// average speed and turning rate of a bird.
+///
+/// Unlike [`Individual::chromosome`], behaviour describes what individual
+/// *does*, so that individuals with very different genes but the same
+/// behaviour are considered alike (and vice versa).
+pub trait BehaviouralIndividual<G = f32>: Individual<G> {
+    fn behaviour(&self) -> Vec<f32>;
+}
+
+/// Archive of behaviours seen so far, for novelty search.
+///
+/// Novelty search rewards individuals for behaving differently from
+/// everyone seen before, rather than for being fit - which helps escaping
+/// deceptive local optima, where fitness alone leads evolution astray.
+///
+/// `GeneticAlgorithm` only ever looks at [`Individual::fitness`], so
+/// novelty computed by [`NoveltyArchive::evaluate`] is meant to be used as
+/// (or mixed into) fitness before evolving the population.
+#[derive(Clone, Debug)]
+pub struct NoveltyArchive {
+    /// Number of the nearest behaviours novelty is averaged over.
+    neighbours: usize,
+    /// Novelty above which behaviour gets archived:
+    /// - 0.0 = every behaviour gets archived
+    /// - larger = only behaviours far from the known ones get archived
+    threshold: f32,
+    /// Maximum number of archived behaviours; when full, the oldest ones
+    /// get dropped first.
+    capacity: Option<usize>,
+    behaviours: Vec<Vec<f32>>,
+}
+
+impl NoveltyArchive {
+    pub fn new(neighbours: usize, threshold: f32) -> Self {
+        assert!(neighbours > 0);
+        assert!(threshold >= 0.0);
+
+        Self { neighbours, threshold, capacity: None, behaviours: Vec::new() }
+    }
+
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0);
+
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Returns archived behaviours, the oldest one first.
+    pub fn behaviours(&self) -> &[Vec<f32>] {
+        &self.behaviours
+    }
+
+    pub fn len(&self) -> usize {
+        self.behaviours.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.behaviours.is_empty()
+    }
+
+    /// Returns novelty of each behaviour, i.e. its mean distance to the
+    /// nearest behaviours among the other given ones and the archived ones.
+    pub fn novelty(&self, behaviours: &[Vec<f32>]) -> Vec<f32> {
+        behaviours
+            .iter()
+            .enumerate()
+            .map(|(idx, behaviour)| {
+                let others = behaviours
+                    .iter()
+                    .enumerate()
+                    .filter(|(other, _)| *other != idx)
+                    .map(|(_, other)| other)
+                    .chain(&self.behaviours);
+
+                let mut distances: Vec<_> =
+                    others.map(|other| distance(behaviour, other)).collect();
+
+                if distances.is_empty() {
+                    return 0.0;
+                }
+
+                distances.sort_by(f32::total_cmp);
+                distances.truncate(self.neighbours);
+
+                distances.iter().sum::<f32>() / distances.len() as f32
+            })
+            .collect()
+    }
+
+    /// Returns novelty of each individual of `population` (in population
+    /// order) and archives behaviours novel enough.
+    pub fn evaluate<G, I>(&mut self, population: &[I]) -> Vec<f32>
+    where
+        I: BehaviouralIndividual<G>,
+    {
+        let behaviours: Vec<_> =
+            population.iter().map(BehaviouralIndividual::behaviour).collect();
+
+        let novelty = self.novelty(&behaviours);
+
+        for (behaviour, &novelty) in behaviours.into_iter().zip(&novelty) {
+            if novelty >= self.threshold {
+                self.behaviours.push(behaviour);
+            }
+        }
+
+        if let Some(capacity) = self.capacity {
+            let excess = self.behaviours.len().saturating_sub(capacity);
+
+            self.behaviours.drain(..excess);
+        }
+
+        novelty
+    }
+}
+
+/// Returns the Euclidean distance between two behaviours.
+fn distance(a: &[f32], b: &[f32]) -> f32 {
+    assert_eq!(a.len(), b.len());
+
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum::<f32>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[derive(Clone, Debug)]
+    struct Walker {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for Walker {
+        fn from_chromosome(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            0.0
+        }
+    }
+
+    impl BehaviouralIndividual for Walker {
+        /// Pretends genes say where the walker ends up.
+        fn behaviour(&self) -> Vec<f32> {
+            self.chromosome.iter().cloned().collect()
+        }
+    }
+
+    fn walkers(positions: &[f32]) -> Vec<Walker> {
+        positions
+            .iter()
+            .map(|&position| {
+                Walker::from_chromosome(vec![position].into_iter().collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_novelty() {
+        let archive = NoveltyArchive::new(2, 0.0);
+        let behaviours = vec![vec![0.0], vec![1.0], vec![3.0], vec![10.0]];
+
+        let actual = archive.novelty(&behaviours);
+        let expected = vec![2.0, 1.5, 2.5, 8.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let mut archive = NoveltyArchive::new(1, 1.5);
+
+        // 0.0 and 1.0 are too close to each other to get archived
+        let novelty = archive.evaluate(&walkers(&[0.0, 1.0, 5.0]));
+
+        assert_relative_eq!(novelty.as_slice(), [1.0, 1.0, 4.0].as_slice());
+        assert_eq!(archive.behaviours(), &[vec![5.0]]);
+
+        // Archived behaviours aren't novel anymore
+        let novelty = archive.evaluate(&walkers(&[5.0, -5.0]));
+
+        assert_relative_eq!(novelty.as_slice(), [0.0, 10.0].as_slice());
+        assert_eq!(archive.behaviours(), &[vec![5.0], vec![-5.0]]);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut archive = NoveltyArchive::new(1, 0.0).with_capacity(2);
+
+        archive.evaluate(&walkers(&[1.0, 2.0, 3.0]));
+
+        assert_eq!(archive.behaviours(), &[vec![2.0], vec![3.0]]);
+    }
+}
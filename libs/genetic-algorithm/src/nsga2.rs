@@ -0,0 +1,525 @@
+use crate::*;
+
+/// Individual judged by several objectives at once, e.g. food eaten and
+/// energy spent.
+///
+/// All objectives are maximized, like [`Individual::fitness`] is; to
+/// minimize one, negate it. `Individual::fitness` is still used wherever a
+/// single number is needed (e.g. by [`Statistics`]).
+pub trait MultiObjectiveIndividual<G = f32>: Individual<G> {
+    fn objectives(&self) -> Vec<f32>;
+}
+
+/// Returns whether `a` is at least as good as `b` in all objectives and
+/// strictly better in at least one of them.
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    assert_eq!(a.len(), b.len());
+
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Splits population into fronts of mutually non-dominated individuals.
+///
+/// The first front is the Pareto front, the second one is what's left after
+/// removing the first one, and so on; fronts contain indices into
+/// `population`.
+pub fn non_dominated_sort<G, I>(population: &[I]) -> Vec<Vec<usize>>
+where
+    I: MultiObjectiveIndividual<G>,
+{
+    let objectives: Vec<_> =
+        population.iter().map(MultiObjectiveIndividual::objectives).collect();
+
+    sort_fronts(&objectives)
+}
+
+/// Returns the non-dominated individuals of given population.
+pub fn pareto_front<G, I>(population: &[I]) -> Vec<&I>
+where
+    I: MultiObjectiveIndividual<G>,
+{
+    non_dominated_sort(population)
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|idx| &population[idx])
+        .collect()
+}
+
+/// Returns how isolated each member of `front` is from its neighbours in
+/// the objective space, in `front` order:
+/// - larger = individual lies in a sparser region
+/// - infinity = individual lies on the boundary of the front
+pub fn crowding_distances(
+    objectives: &[Vec<f32>],
+    front: &[usize],
+) -> Vec<f32> {
+    let mut distances = vec![0.0; front.len()];
+
+    if front.is_empty() {
+        return distances;
+    }
+
+    let mut order: Vec<_> = (0..front.len()).collect();
+
+    // Values of each objective, in `front` order
+    let columns = (0..objectives[front[0]].len()).map(|objective| {
+        front.iter().map(|&idx| objectives[idx][objective]).collect::<Vec<_>>()
+    });
+
+    for values in columns {
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+        let first = order[0];
+        let last = order[order.len() - 1];
+        let range = values[last] - values[first];
+
+        distances[first] = f32::INFINITY;
+        distances[last] = f32::INFINITY;
+
+        if range <= 0.0 {
+            continue;
+        }
+
+        for window in order.windows(3) {
+            distances[window[1]] +=
+                (values[window[2]] - values[window[0]]) / range;
+        }
+    }
+
+    distances
+}
+
+fn sort_fronts(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    let len = objectives.len();
+
+    // For each individual: whom it dominates and by how many it's dominated
+    let mut dominated = vec![Vec::new(); len];
+    let mut domination_counts = vec![0; len];
+
+    for a in 0..len {
+        for b in 0..len {
+            if dominates(&objectives[a], &objectives[b]) {
+                dominated[a].push(b);
+            } else if dominates(&objectives[b], &objectives[a]) {
+                domination_counts[a] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+
+    let mut front: Vec<_> =
+        (0..len).filter(|&idx| domination_counts[idx] == 0).collect();
+
+    while !front.is_empty() {
+        let mut next = Vec::new();
+
+        for &a in &front {
+            for &b in &dominated[a] {
+                domination_counts[b] -= 1;
+
+                if domination_counts[b] == 0 {
+                    next.push(b);
+                }
+            }
+        }
+
+        fronts.push(front);
+        front = next;
+    }
+
+    fronts
+}
+
+/// Front index and crowding distance of each individual, in population
+/// order.
+fn rank(objectives: &[Vec<f32>]) -> Vec<(usize, f32)> {
+    let mut ranks = vec![(0, 0.0); objectives.len()];
+
+    for (front_idx, front) in sort_fronts(objectives).iter().enumerate() {
+        let distances = crowding_distances(objectives, front);
+
+        for (&idx, distance) in front.iter().zip(distances) {
+            ranks[idx] = (front_idx, distance);
+        }
+    }
+
+    ranks
+}
+
+/// Crowded-comparison: lower front wins, ties are broken by larger crowding
+/// distance.
+fn is_better(a: (usize, f32), b: (usize, f32)) -> bool {
+    a.0 < b.0 || (a.0 == b.0 && a.1 > b.1)
+}
+
+/// NSGA-II's selection: binary tournaments using the crowded comparison for
+/// parents, and fronts filled by crowding distance for survivors.
+#[derive(Clone, Debug, Default)]
+pub struct Nsga2Selection;
+
+impl Nsga2Selection {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Picks `count` parents, each being the better one of two random
+    /// individuals.
+    pub fn select_many<'a, G, I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I>
+    where
+        I: MultiObjectiveIndividual<G>,
+    {
+        assert!(!population.is_empty());
+
+        let objectives: Vec<_> = population
+            .iter()
+            .map(MultiObjectiveIndividual::objectives)
+            .collect();
+
+        let ranks = rank(&objectives);
+
+        (0..count)
+            .map(|_| {
+                let a = rng.gen_range(0..population.len());
+                let b = rng.gen_range(0..population.len());
+
+                if is_better(ranks[b], ranks[a]) {
+                    &population[b]
+                } else {
+                    &population[a]
+                }
+            })
+            .collect()
+    }
+
+    /// Picks `count` survivors among `candidates`, front by front; when a
+    /// front doesn't fit as a whole, its least crowded members are
+    /// preferred.
+    pub fn survivors<G, I>(&self, candidates: Vec<I>, count: usize) -> Vec<I>
+    where
+        I: MultiObjectiveIndividual<G>,
+    {
+        let objectives: Vec<_> = candidates
+            .iter()
+            .map(MultiObjectiveIndividual::objectives)
+            .collect();
+
+        let ranks = rank(&objectives);
+
+        let mut order: Vec<_> = (0..candidates.len()).collect();
+
+        order.sort_by(|&a, &b| {
+            let (front_a, distance_a) = ranks[a];
+            let (front_b, distance_b) = ranks[b];
+
+            front_a.cmp(&front_b).then(distance_b.total_cmp(&distance_a))
+        });
+
+        // Survivors are returned in their original order
+        let mut survives = vec![false; candidates.len()];
+
+        for idx in order.into_iter().take(count) {
+            survives[idx] = true;
+        }
+
+        candidates
+            .into_iter()
+            .zip(survives)
+            .filter_map(|(candidate, survives)| survives.then_some(candidate))
+            .collect()
+    }
+}
+
+/// Non-dominated sorting genetic algorithm II.
+///
+/// Every generation breeds as many offspring as there are individuals and
+/// then keeps the best half of parents and offspring combined, so offspring
+/// have to be evaluated by `Individual::from_chromosome`.
+#[derive(Debug)]
+pub struct Nsga2<C, M> {
+    selection: Nsga2Selection,
+    crossover_method: C,
+    mutation_method: M,
+    /// Number of generations evolved so far.
+    generation: usize,
+}
+
+impl<C, M> Nsga2<C, M> {
+    pub fn new(crossover_method: C, mutation_method: M) -> Self {
+        Self {
+            selection: Nsga2Selection::new(),
+            crossover_method,
+            mutation_method,
+            generation: 0,
+        }
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn evolve<G, I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Vec<I>
+    where
+        G: Gene,
+        I: MultiObjectiveIndividual<G>,
+        C: CrossoverMethod<G>,
+        M: MutationMethod<G>,
+    {
+        assert!(!population.is_empty());
+
+        let parents =
+            self.selection.select_many(rng, population, 2 * population.len());
+
+        let mut successes = 0;
+
+        let offspring: Vec<_> = parents
+            .chunks(2)
+            .map(|parents| {
+                let parent_a = parents[0].chromosome();
+                let parent_b = parents[1].chromosome();
+
+                let mut child =
+                    self.crossover_method.crossover(rng, parent_a, parent_b);
+
+                child.inherit_step_sizes(parent_a, parent_b);
+                self.mutation_method.mutate(rng, &mut child);
+
+                let child = I::from_chromosome(child);
+                let objectives = child.objectives();
+
+                if parents
+                    .iter()
+                    .all(|parent| dominates(&objectives, &parent.objectives()))
+                {
+                    successes += 1;
+                }
+
+                child
+            })
+            .collect();
+
+        self.generation += 1;
+        self.mutation_method
+            .adapt(self.generation, successes as f32 / offspring.len() as f32);
+
+        let candidates =
+            population.iter().map(clone_individual).chain(offspring).collect();
+
+        self.selection.survivors(candidates, population.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Individual whose objectives are its genes.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Point {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for Point {
+        fn from_chromosome(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            self.chromosome.iter().sum()
+        }
+    }
+
+    impl MultiObjectiveIndividual for Point {
+        fn objectives(&self) -> Vec<f32> {
+            self.chromosome.iter().cloned().collect()
+        }
+    }
+
+    /// Schaffer's problem: minimize both `x^2` and `(x - 2)^2`; every `x`
+    /// within `0.0..=2.0` is Pareto-optimal.
+    #[derive(Clone, Debug)]
+    struct Schaffer {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for Schaffer {
+        fn from_chromosome(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            self.objectives().iter().sum()
+        }
+    }
+
+    impl MultiObjectiveIndividual for Schaffer {
+        fn objectives(&self) -> Vec<f32> {
+            let x = self.chromosome[0];
+
+            vec![-x.powi(2), -(x - 2.0).powi(2)]
+        }
+    }
+
+    fn points(objectives: &[[f32; 2]]) -> Vec<Point> {
+        objectives
+            .iter()
+            .map(|objectives| {
+                Point::from_chromosome(objectives.iter().cloned().collect())
+            })
+            .collect()
+    }
+
+    fn population() -> Vec<Point> {
+        points(&[
+            [1.0, 5.0],
+            [2.0, 4.0],
+            [3.0, 3.0],
+            [1.0, 1.0],
+            [2.0, 2.0],
+            [0.0, 0.0],
+        ])
+    }
+
+    #[test]
+    fn test_dominates() {
+        assert!(dominates(&[2.0, 2.0], &[1.0, 2.0]));
+        assert!(!dominates(&[1.0, 2.0], &[2.0, 2.0]));
+        assert!(!dominates(&[2.0, 2.0], &[2.0, 2.0]));
+        assert!(!dominates(&[3.0, 1.0], &[1.0, 3.0]));
+    }
+
+    #[test]
+    fn test_non_dominated_sort() {
+        let fronts = non_dominated_sort(&population());
+
+        assert_eq!(fronts, vec![vec![0, 1, 2], vec![4], vec![3], vec![5]]);
+    }
+
+    #[test]
+    fn test_pareto_front() {
+        let population = population();
+        let front = pareto_front(&population);
+
+        assert_eq!(front, vec![&population[0], &population[1], &population[2]]);
+        assert!(pareto_front::<f32, Point>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_crowding_distances() {
+        let objectives = vec![
+            vec![1.0, 5.0],
+            vec![2.0, 4.0],
+            vec![3.0, 3.0],
+            vec![2.5, 3.5],
+        ];
+
+        let distances = crowding_distances(&objectives, &[0, 1, 2, 3]);
+
+        assert_eq!(distances[0], f32::INFINITY);
+        assert_eq!(distances[2], f32::INFINITY);
+        assert_relative_eq!(distances[1], 1.5 / 2.0 + 1.5 / 2.0);
+        assert_relative_eq!(distances[3], 1.0 / 2.0 + 1.0 / 2.0);
+    }
+
+    #[test]
+    fn test_survivors() {
+        let candidates = points(&[
+            [1.0, 5.0],
+            [0.0, 0.0],
+            [2.0, 4.0],
+            [3.0, 3.0],
+            [2.5, 3.5],
+            [2.0, 2.0],
+        ]);
+
+        let survivors = Nsga2Selection::new().survivors(candidates.clone(), 3);
+
+        // The Pareto front doesn't fit as a whole, so its most crowded
+        // member, [2.5, 3.5], gets dropped
+        assert_eq!(
+            survivors,
+            vec![
+                candidates[0].clone(),
+                candidates[2].clone(),
+                candidates[3].clone()
+            ]
+        );
+
+        let survivors = Nsga2Selection::new().survivors(candidates.clone(), 5);
+
+        assert_eq!(survivors.len(), 5);
+        assert!(!survivors.contains(&candidates[1]));
+    }
+
+    #[test]
+    fn test_select_many() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        let selected =
+            Nsga2Selection::new().select_many(&mut rng, &population, 1000);
+
+        // The last individual is dominated by everyone, so it can only win a
+        // tournament against itself (~1/36th of them)
+        let worst = selected
+            .iter()
+            .filter(|&&individual| *individual == population[5])
+            .count();
+
+        assert_eq!(selected.len(), 1000);
+        assert_eq!(worst, 29);
+    }
+
+    #[test]
+    fn test_evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut nsga2 = Nsga2::new(
+            ArithmeticCrossover::new(0.5),
+            GaussianMutation::new(1.0, 0.5),
+        );
+
+        let mut population: Vec<_> = (0..20)
+            .map(|_| {
+                let x = rng.gen_range(-10.0..10.0);
+
+                Schaffer::from_chromosome(vec![x].into_iter().collect())
+            })
+            .collect();
+
+        for _ in 0..30 {
+            population = nsga2.evolve(&mut rng, &population);
+        }
+
+        let front = pareto_front(&population);
+
+        assert_eq!(nsga2.generation(), 30);
+        assert_eq!(population.len(), 20);
+        assert!(front.len() > 10);
+        assert!(front.iter().all(
+            |individual| (-0.1..=2.1).contains(&individual.chromosome()[0])
+        ));
+    }
+}
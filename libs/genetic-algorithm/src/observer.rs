@@ -0,0 +1,234 @@
+use crate::*;
+use std::fmt;
+
+/// Gets notified about what's going on inside [`GeneticAlgorithm::evolve`],
+/// e.g. to log progress, write checkpoints or visualise evolution.
+///
+/// All hooks do nothing by default, so implementors override only those
+/// they care about.
+pub trait EvolutionObserver<G = f32> {
+    /// Called before anything else happens in given generation; generations
+    /// are counted from zero.
+    fn on_generation_start(&mut self, _generation: usize) {}
+
+    /// Called with parents of all offspring, in pairs.
+    fn on_parents_selected(&mut self, _parents: &[&Chromosome<G>]) {}
+
+    /// Called for every child, once it's been mutated and evaluated.
+    fn on_child_created(&mut self, _child: &Chromosome<G>, _fitness: f32) {}
+
+    /// Called once the next generation is ready, with the statistics the
+    /// algorithm's `evolve` returns.
+    fn on_generation_end(
+        &mut self,
+        _generation: usize,
+        _statistics: &Statistics,
+    ) {
+    }
+
+    /// Tells [`Evolution`] to stop before the next generation, regardless of
+    /// its termination condition.
+    fn should_stop(&self) -> bool {
+        false
+    }
+}
+
+/// Observer that ignores everything.
+#[derive(Clone, Debug, Default)]
+pub struct NoObserver;
+
+impl NoObserver {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> EvolutionObserver<G> for NoObserver {}
+
+/// Several observers, notified in the order they were added.
+///
+/// Useful when observers have to be added at runtime, e.g. by whoever owns
+/// a [`GeneticAlgorithm`] created somewhere else.
+pub struct Observers<G = f32> {
+    observers: Vec<Box<dyn EvolutionObserver<G>>>,
+}
+
+impl<G> Observers<G> {
+    pub fn new() -> Self {
+        Self { observers: Vec::new() }
+    }
+
+    pub fn with(
+        mut self,
+        observer: impl EvolutionObserver<G> + 'static,
+    ) -> Self {
+        self.push(observer);
+        self
+    }
+
+    pub fn push(&mut self, observer: impl EvolutionObserver<G> + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
+    pub fn len(&self) -> usize {
+        self.observers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.observers.is_empty()
+    }
+}
+
+impl<G> Default for Observers<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G> fmt::Debug for Observers<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observers").field("len", &self.len()).finish()
+    }
+}
+
+impl<G> EvolutionObserver<G> for Observers<G> {
+    fn on_generation_start(&mut self, generation: usize) {
+        for observer in &mut self.observers {
+            observer.on_generation_start(generation);
+        }
+    }
+
+    fn on_parents_selected(&mut self, parents: &[&Chromosome<G>]) {
+        for observer in &mut self.observers {
+            observer.on_parents_selected(parents);
+        }
+    }
+
+    fn on_child_created(&mut self, child: &Chromosome<G>, fitness: f32) {
+        for observer in &mut self.observers {
+            observer.on_child_created(child, fitness);
+        }
+    }
+
+    fn on_generation_end(
+        &mut self,
+        generation: usize,
+        statistics: &Statistics,
+    ) {
+        for observer in &mut self.observers {
+            observer.on_generation_end(generation, statistics);
+        }
+    }
+
+    fn should_stop(&self) -> bool {
+        self.observers.iter().any(|observer| observer.should_stop())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Records names of called hooks.
+    #[derive(Clone, Default)]
+    struct Recorder {
+        events: Rc<RefCell<Vec<String>>>,
+        stop_after: Option<usize>,
+    }
+
+    impl EvolutionObserver for Recorder {
+        fn on_generation_start(&mut self, generation: usize) {
+            self.events.borrow_mut().push(format!("start {}", generation));
+        }
+
+        fn on_parents_selected(&mut self, parents: &[&Chromosome]) {
+            self.events.borrow_mut().push(format!("parents {}", parents.len()));
+        }
+
+        fn on_child_created(&mut self, _child: &Chromosome, _fitness: f32) {
+            self.events.borrow_mut().push("child".into());
+        }
+
+        fn on_generation_end(&mut self, generation: usize, _: &Statistics) {
+            self.events.borrow_mut().push(format!("end {}", generation));
+        }
+
+        fn should_stop(&self) -> bool {
+            let ends = self
+                .events
+                .borrow()
+                .iter()
+                .filter(|event| event.starts_with("end"))
+                .count();
+
+            self.stop_after.map_or(false, |stop_after| ends >= stop_after)
+        }
+    }
+
+    fn population() -> Vec<TestIndividual> {
+        (0..3)
+            .map(|n| {
+                TestIndividual::from_chromosome(
+                    vec![n as f32].into_iter().collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hooks() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let recorder = Recorder::default();
+
+        let mut ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.1),
+        )
+        .with_elitism(1)
+        .with_observer(Observers::new().with(recorder.clone()));
+
+        let population = ga.evolve(&mut rng, &population()).0;
+        ga.evolve(&mut rng, &population);
+
+        assert_eq!(
+            *recorder.events.borrow(),
+            vec![
+                "start 0",
+                "parents 4",
+                "child",
+                "child",
+                "end 0",
+                "start 1",
+                "parents 4",
+                "child",
+                "child",
+                "end 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_early_stopping() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let recorder = Recorder { stop_after: Some(3), ..Default::default() };
+
+        let ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.1),
+        )
+        .with_observer(recorder);
+
+        let result = Evolution::new(ga, population())
+            .with_termination(Termination::MaxGenerations(100))
+            .run(&mut rng);
+
+        assert_eq!(result.generations(), 3);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::*;
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+
+impl<S, C, M, R, N, O, F, G> GeneticAlgorithm<S, C, M, R, N, O, F, G>
+where
+    S: SelectionMethod,
+    R: ReplacementMethod,
+    F: FitnessScaling,
+{
+    /// Works like [`GeneticAlgorithm::evolve`], but breeds offspring on
+    /// rayon's thread pool; see [`GeneticAlgorithm::try_par_evolve`] for a
+    /// fallible version.
+    ///
+    /// Parents are still selected sequentially using `rng`; afterwards a
+    /// master seed is drawn from it and every child gets its own random
+    /// number stream derived from that seed and the child's index. This
+    /// way results depend only on `rng`, not on the number of threads or
+    /// on how work got scheduled.
+    ///
+    /// `Individual::from_chromosome` gets called in parallel as well, so
+    /// that's the place to evaluate costly fitness functions.
+    ///
+    /// The observer gets notified on the calling thread (so it doesn't have
+    /// to be `Sync`), about all offspring at once, after they've been bred.
+    pub fn par_evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, Statistics)
+    where
+        G: Gene + Send + Sync,
+        I: Individual<G> + Send + Sync,
+        C: CrossoverMethod<G> + Sync,
+        M: MutationMethod<G> + Sync,
+        N: NichingMethod<G>,
+        O: EvolutionObserver<G>,
+    {
+        match self.try_par_evolve(rng, population) {
+            Ok(evolved) => evolved,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Works like [`GeneticAlgorithm::par_evolve`], but returns an error
+    /// when parents can't be selected; see
+    /// [`GeneticAlgorithm::try_evolve`].
+    pub fn try_par_evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), SelectionError>
+    where
+        G: Gene + Send + Sync,
+        I: Individual<G> + Send + Sync,
+        C: CrossoverMethod<G> + Sync,
+        M: MutationMethod<G> + Sync,
+        N: NichingMethod<G>,
+        O: EvolutionObserver<G>,
+    {
+        self.check(population)?;
+
+        let success_rate = self.success_rate(population);
+        let ranked = self.rank(population);
+        let parents = self.select_parents(rng, population, &ranked)?;
+        let seed = rng.gen();
+
+        self.adapt(success_rate);
+
+        let breeder = self.breeder();
+
+        let offspring = parents
+            .par_chunks(2)
+            .enumerate()
+            .map(|(idx, parents)| {
+                let mut rng = ChaCha8Rng::from_seed(seed);
+                rng.set_stream(idx as u64);
+
+                breeder.breed(&mut rng, parents[0], parents[1])
+            })
+            .collect();
+
+        Ok(self.next_generation(&ranked, offspring))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::ThreadPoolBuilder;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Observer counting offspring; can't be shared between threads.
+    #[derive(Clone, Default)]
+    struct ChildCounter {
+        children: Rc<Cell<usize>>,
+    }
+
+    impl EvolutionObserver for ChildCounter {
+        fn on_child_created(&mut self, _child: &Chromosome, _fitness: f32) {
+            self.children.set(self.children.get() + 1);
+        }
+    }
+
+    fn population() -> Vec<TestIndividual> {
+        (0..50)
+            .map(|n| {
+                let genes = (0..10).map(|gene| ((n * gene) % 7) as f32);
+
+                TestIndividual::from_chromosome(genes.collect())
+            })
+            .collect()
+    }
+
+    fn evolve(threads: usize) -> Vec<TestIndividual> {
+        let pool =
+            ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+
+        pool.install(|| {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let mut ga = GeneticAlgorithm::new(
+                TournamentSelection::new(3, 1.0),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_elitism(2);
+
+            let mut population = population();
+
+            for _ in 0..10 {
+                population = ga.par_evolve(&mut rng, &population).0;
+            }
+
+            population
+        })
+    }
+
+    #[test]
+    fn test_par_evolve_is_deterministic() {
+        let expected = evolve(1);
+
+        assert_eq!(evolve(2), expected);
+        assert_eq!(evolve(8), expected);
+    }
+
+    #[test]
+    fn test_par_evolve_improves_fitness() {
+        let initial = Statistics::new(&population());
+        let evolved = Statistics::new(&evolve(4));
+
+        assert!(evolved.mean_fitness() > initial.mean_fitness());
+        assert!(evolved.max_fitness() >= initial.max_fitness());
+    }
+
+    #[test]
+    fn test_par_evolve_notifies_observer() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let counter = ChildCounter::default();
+
+        let mut ga = GeneticAlgorithm::new(
+            TournamentSelection::new(3, 1.0),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_elitism(2)
+        .with_observer(Observers::new().with(counter.clone()));
+
+        let population = ga.par_evolve(&mut rng, &population()).0;
+        ga.par_evolve(&mut rng, &population);
+
+        assert_eq!(counter.children.get(), 2 * 48);
+    }
+
+    #[test]
+    fn test_try_par_evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        let population: Vec<_> = [[1.0, 2.0], [-1.0, -2.0]]
+            .iter()
+            .map(|genes| {
+                TestIndividual::from_chromosome(genes.iter().cloned().collect())
+            })
+            .collect();
+
+        assert_eq!(
+            ga.try_par_evolve(&mut rng, &population).map(|_| ()),
+            Err(SelectionError::NegativeFitness { fitness: -3.0 })
+        );
+
+        assert_eq!(
+            ga.try_par_evolve(&mut rng, &Vec::<TestIndividual>::new())
+                .map(|_| ()),
+            Err(SelectionError::EmptyPopulation)
+        );
+    }
+}
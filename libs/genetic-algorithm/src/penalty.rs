@@ -0,0 +1,94 @@
+use crate::*;
+
+/// Hook for constraints that can't be enforced on genes directly and have to
+/// be handled on the fitness level instead.
+///
+/// `GeneticAlgorithm` only ever looks at [`Individual::fitness`], so the
+/// penalty is meant to be applied from there, e.g.:
+///
+/// ```ignore
+/// fn fitness(&self) -> f32 {
+///     PENALTY.penalize(self.raw_fitness, &self.chromosome)
+/// }
+/// ```
+pub trait PenaltyFunction<G = f32> {
+    /// Returns how much should be taken away from the fitness of given
+    /// chromosome:
+    /// - 0.0 = no constraints are violated
+    /// - larger = constraints are violated more severely
+    fn penalty(&self, chromosome: &Chromosome<G>) -> f32;
+
+    fn penalize(&self, fitness: f32, chromosome: &Chromosome<G>) -> f32 {
+        fitness - self.penalty(chromosome)
+    }
+}
+
+impl<F, G> PenaltyFunction<G> for F
+where
+    F: Fn(&Chromosome<G>) -> f32,
+{
+    fn penalty(&self, chromosome: &Chromosome<G>) -> f32 {
+        self(chromosome)
+    }
+}
+
+/// Penalizes genes that stick out of their bounds, proportionally to how far
+/// out they are.
+#[derive(Clone, Debug)]
+pub struct BoundsPenalty {
+    bounds: GeneBounds,
+    /// Penalty per unit of distance outside of bounds:
+    /// - 0.0 = violations aren't penalized at all
+    /// - 10.0 = each gene costs 10.0 per unit it's out of bounds
+    weight: f32,
+}
+
+impl BoundsPenalty {
+    pub fn new(bounds: GeneBounds, weight: f32) -> Self {
+        assert!(weight >= 0.0);
+
+        Self { bounds, weight }
+    }
+}
+
+impl<G: Gene> PenaltyFunction<G> for BoundsPenalty {
+    fn penalty(&self, chromosome: &Chromosome<G>) -> f32 {
+        self.weight * self.bounds.violation(chromosome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn chromosome(genes: &[f32]) -> Chromosome {
+        genes.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_bounds_penalty() {
+        let penalty = BoundsPenalty::new(GeneBounds::uniform(0.0, 1.0), 2.0);
+
+        assert_relative_eq!(penalty.penalty(&chromosome(&[0.5, 1.0])), 0.0);
+        assert_relative_eq!(penalty.penalty(&chromosome(&[-0.5, 1.5])), 2.0);
+        assert_relative_eq!(
+            penalty.penalize(10.0, &chromosome(&[-0.5, 1.5])),
+            8.0
+        );
+    }
+
+    #[test]
+    fn test_closure() {
+        // Sum of genes must not exceed 1.0
+        let penalty = |chromosome: &Chromosome| {
+            (chromosome.iter().sum::<f32>() - 1.0).max(0.0)
+        };
+
+        assert_relative_eq!(penalty.penalize(5.0, &chromosome(&[0.25])), 5.0);
+        assert_relative_eq!(
+            penalty.penalize(5.0, &chromosome(&[1.0, 1.5])),
+            3.5
+        );
+    }
+}
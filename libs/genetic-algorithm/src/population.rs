@@ -0,0 +1,257 @@
+use crate::*;
+use rand_chacha::ChaCha8Rng;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+
+/// Snapshot of a run, from which it can be resumed later.
+///
+/// Besides the population itself, the snapshot contains the state of the
+/// algorithm and of the random number generator, so that a resumed run
+/// continues exactly as the original one would have.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Population<G = f32, M = GaussianMutation> {
+    chromosomes: Vec<Chromosome<G>>,
+    fitnesses: Vec<f32>,
+    state: AlgorithmState<G, M>,
+    rng: ChaCha8Rng,
+}
+
+impl<G, M> Population<G, M>
+where
+    G: Clone + Serialize + DeserializeOwned,
+    M: Clone + Serialize + DeserializeOwned,
+{
+    pub fn new<I: Individual<G>>(
+        population: &[I],
+        state: AlgorithmState<G, M>,
+        rng: &ChaCha8Rng,
+    ) -> Self {
+        Self {
+            chromosomes: population
+                .iter()
+                .map(|individual| individual.chromosome().clone())
+                .collect(),
+            fitnesses: population.iter().map(Individual::fitness).collect(),
+            state,
+            rng: rng.clone(),
+        }
+    }
+
+    pub fn chromosomes(&self) -> &[Chromosome<G>] {
+        &self.chromosomes
+    }
+
+    /// Returns fitnesses individuals had when the snapshot was taken.
+    pub fn fitnesses(&self) -> &[f32] {
+        &self.fitnesses
+    }
+
+    pub fn generation(&self) -> usize {
+        self.state.generation
+    }
+
+    /// Returns state of the algorithm, to be restored with
+    /// [`GeneticAlgorithm::with_state`].
+    pub fn state(&self) -> AlgorithmState<G, M> {
+        self.state.clone()
+    }
+
+    /// Returns the random number generator, in the state it was when the
+    /// snapshot was taken.
+    pub fn rng(&self) -> ChaCha8Rng {
+        self.rng.clone()
+    }
+
+    /// Re-creates individuals from their chromosomes.
+    ///
+    /// Note that individuals whose fitness isn't computed in
+    /// `Individual::from_chromosome` have to be re-evaluated (or given
+    /// [`Population::fitnesses`]) before evolving them further.
+    pub fn individuals<I: Individual<G>>(&self) -> Vec<I> {
+        self.chromosomes.iter().cloned().map(I::from_chromosome).collect()
+    }
+
+    /// Writes the snapshot as JSON.
+    pub fn save(&self, writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Reads a snapshot written by [`Population::save`].
+    pub fn load(reader: impl Read) -> io::Result<Self> {
+        serde_json::from_reader(reader).map_err(io::Error::from)
+    }
+}
+
+/// Part of a [`GeneticAlgorithm`] that changes as it evolves: the number
+/// of generations evolved so far, the mutation method (which may adapt
+/// over time) and offspring the algorithm waits to learn from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AlgorithmState<G = f32, M = GaussianMutation> {
+    generation: usize,
+    mutation_method: M,
+    pending: Option<Pending<G>>,
+}
+
+impl<S, C, M, R, N, O, F, G> GeneticAlgorithm<S, C, M, R, N, O, F, G>
+where
+    S: SelectionMethod,
+    R: ReplacementMethod,
+    F: FitnessScaling,
+{
+    /// Returns the part of the algorithm that changes as it evolves, e.g.
+    /// to be put into a [`Population`] snapshot.
+    pub fn state(&self) -> AlgorithmState<G, M>
+    where
+        G: Clone,
+        M: Clone,
+    {
+        AlgorithmState {
+            generation: self.generation,
+            mutation_method: self.mutation_method.clone(),
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// Restores state returned by [`GeneticAlgorithm::state`], e.g. when
+    /// resuming a run from a [`Population`] snapshot.
+    pub fn with_state(mut self, state: AlgorithmState<G, M>) -> Self {
+        self.generation = state.generation;
+        self.mutation_method = state.mutation_method;
+        self.pending = state.pending;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    type Algorithm = GeneticAlgorithm<
+        RouletteWheelSelection,
+        UniformCrossover,
+        GaussianMutation,
+    >;
+
+    fn algorithm() -> Algorithm {
+        GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.5),
+        )
+        .with_elitism(1)
+    }
+
+    fn evolve(
+        ga: &mut Algorithm,
+        rng: &mut ChaCha8Rng,
+        mut population: Vec<TestIndividual>,
+    ) -> Vec<TestIndividual> {
+        for _ in 0..5 {
+            population = ga.evolve(rng, &population).0;
+        }
+
+        population
+    }
+
+    #[test]
+    fn test_resume() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut ga = algorithm();
+
+        let population: Vec<TestIndividual> = (0..10)
+            .map(|_| {
+                let genes = (0..5).map(|_| rng.gen_range(0.0..1.0));
+
+                TestIndividual::from_chromosome(genes.collect())
+            })
+            .collect();
+
+        let population = evolve(&mut ga, &mut rng, population);
+
+        let mut checkpoint = Vec::new();
+
+        Population::new(&population, ga.state(), &rng)
+            .save(&mut checkpoint)
+            .unwrap();
+
+        let expected = evolve(&mut ga, &mut rng, population);
+
+        // Resume in a fresh algorithm, as if the process got restarted
+        let snapshot = Population::load(checkpoint.as_slice()).unwrap();
+        let mut ga = algorithm().with_state(snapshot.state());
+        let mut rng = snapshot.rng();
+
+        assert_eq!(snapshot.generation(), 5);
+        assert_eq!(snapshot.fitnesses().len(), 10);
+
+        let actual = evolve(&mut ga, &mut rng, snapshot.individuals());
+
+        assert_eq!(actual, expected);
+        assert_eq!(ga.generation(), 10);
+    }
+
+    #[test]
+    fn test_resume_with_adaptive_mutation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let algorithm = || {
+            GeneticAlgorithm::new(
+                TournamentSelection::new(2, 1.0),
+                UniformCrossover::new(),
+                OneFifthRuleMutation::new(0.5, 0.5, 0.5),
+            )
+        };
+
+        let mut ga = algorithm();
+
+        let mut population: Vec<TestIndividual> = (0..10)
+            .map(|_| {
+                let genes = (0..5).map(|_| rng.gen_range(0.0..1.0));
+
+                TestIndividual::from_chromosome(genes.collect())
+            })
+            .collect();
+
+        for _ in 0..5 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        let mut checkpoint = Vec::new();
+
+        Population::new(&population, ga.state(), &rng)
+            .save(&mut checkpoint)
+            .unwrap();
+
+        let mut expected = population;
+
+        for _ in 0..5 {
+            expected = ga.evolve(&mut rng, &expected).0;
+        }
+
+        let snapshot = Population::load(checkpoint.as_slice()).unwrap();
+        let mut resumed = algorithm().with_state(snapshot.state());
+        let mut rng = snapshot.rng();
+        let mut actual: Vec<TestIndividual> = snapshot.individuals();
+
+        for _ in 0..5 {
+            actual = resumed.evolve(&mut rng, &actual).0;
+        }
+
+        // Mutation's magnitude and offspring it learns from are part of the
+        // snapshot, so the resumed run goes exactly the same way
+        assert_eq!(actual, expected);
+        assert_eq!(
+            resumed.mutation_method.coeff(),
+            ga.mutation_method.coeff()
+        );
+    }
+
+    #[test]
+    fn test_load_garbage() {
+        let err = Population::<f32>::load("{}".as_bytes()).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
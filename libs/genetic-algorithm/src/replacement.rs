@@ -0,0 +1,308 @@
+use crate::*;
+use std::rc::Rc;
+
+/// Decides which individuals make it into the next generation.
+///
+/// Methods that compare offspring against each other or against their
+/// parents ([`MuPlusLambdaReplacement`] and [`MuCommaLambdaReplacement`])
+/// rely on [`Individual::fitness`] of freshly created offspring, so they're
+/// meaningful only when `Individual::from_chromosome` yields an already
+/// evaluated individual. [`GenerationalReplacement`] and
+/// [`SteadyStateReplacement`] don't look at offspring's fitness, so they
+/// work also when fitness gets computed later (e.g. by running a
+/// simulation).
+pub trait ReplacementMethod {
+    /// Returns how many offspring have to be bred so that `count` slots of
+    /// the next generation can be filled.
+    fn offspring_count(&self, count: usize) -> usize;
+
+    /// Picks `count` survivors among `parents` and `offspring`.
+    fn replace<G: Gene, I: Individual<G> + Clone>(
+        &self,
+        parents: &[&I],
+        offspring: Vec<I>,
+        count: usize,
+    ) -> Vec<I>;
+}
+
+/// Offspring replace the whole population.
+#[derive(Clone, Debug, Default)]
+pub struct GenerationalReplacement;
+
+impl GenerationalReplacement {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ReplacementMethod for GenerationalReplacement {
+    fn offspring_count(&self, count: usize) -> usize {
+        count
+    }
+
+    fn replace<G: Gene, I: Individual<G> + Clone>(
+        &self,
+        _parents: &[&I],
+        mut offspring: Vec<I>,
+        count: usize,
+    ) -> Vec<I> {
+        offspring.truncate(count);
+        offspring
+    }
+}
+
+/// (μ+λ) strategy: `lambda` offspring are bred and the best individuals
+/// among both parents and offspring survive.
+#[derive(Clone, Debug)]
+pub struct MuPlusLambdaReplacement {
+    lambda: usize,
+}
+
+impl MuPlusLambdaReplacement {
+    pub fn new(lambda: usize) -> Self {
+        assert!(lambda > 0);
+
+        Self { lambda }
+    }
+}
+
+impl ReplacementMethod for MuPlusLambdaReplacement {
+    fn offspring_count(&self, _count: usize) -> usize {
+        self.lambda
+    }
+
+    fn replace<G: Gene, I: Individual<G> + Clone>(
+        &self,
+        parents: &[&I],
+        offspring: Vec<I>,
+        count: usize,
+    ) -> Vec<I> {
+        let parents = parents.iter().map(|parent| Candidate::Parent(*parent));
+        let offspring = offspring.into_iter().map(Candidate::Offspring);
+        let mut candidates: Vec<_> = parents.chain(offspring).collect();
+
+        candidates.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+        candidates.truncate(count);
+        candidates.into_iter().map(Candidate::into_individual).collect()
+    }
+}
+
+/// (μ,λ) strategy: `lambda` offspring are bred and only the best of them
+/// survive; parents are always discarded.
+#[derive(Clone, Debug)]
+pub struct MuCommaLambdaReplacement {
+    lambda: usize,
+}
+
+impl MuCommaLambdaReplacement {
+    pub fn new(lambda: usize) -> Self {
+        assert!(lambda > 0);
+
+        Self { lambda }
+    }
+}
+
+impl ReplacementMethod for MuCommaLambdaReplacement {
+    fn offspring_count(&self, count: usize) -> usize {
+        // There must be enough offspring to fill the whole generation
+        self.lambda.max(count)
+    }
+
+    fn replace<G: Gene, I: Individual<G> + Clone>(
+        &self,
+        _parents: &[&I],
+        mut offspring: Vec<I>,
+        count: usize,
+    ) -> Vec<I> {
+        offspring.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+        offspring.truncate(count);
+        offspring
+    }
+}
+
+/// Steady-state strategy: only `offspring` children are bred each
+/// generation and they replace the worst parents.
+#[derive(Clone, Debug)]
+pub struct SteadyStateReplacement {
+    offspring: usize,
+}
+
+impl SteadyStateReplacement {
+    pub fn new(offspring: usize) -> Self {
+        assert!(offspring > 0);
+
+        Self { offspring }
+    }
+}
+
+impl ReplacementMethod for SteadyStateReplacement {
+    fn offspring_count(&self, count: usize) -> usize {
+        self.offspring.min(count)
+    }
+
+    fn replace<G: Gene, I: Individual<G> + Clone>(
+        &self,
+        parents: &[&I],
+        offspring: Vec<I>,
+        count: usize,
+    ) -> Vec<I> {
+        let mut parents = parents.to_vec();
+
+        parents.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+        parents.truncate(count.saturating_sub(offspring.len()));
+
+        parents.into_iter().cloned().chain(offspring).collect()
+    }
+}
+
+/// Re-creates an individual from its chromosome, since `Individual`
+/// doesn't require `Clone`.
+pub(crate) fn clone_individual<G: Clone, I: Individual<G>>(
+    individual: &I,
+) -> I {
+    I::from_chromosome(individual.chromosome().clone())
+}
+
+/// Parent or child competing for a place in the next generation.
+pub(crate) enum Contender<'a, I> {
+    Parent(&'a I),
+    /// Child along with fitness of its fitter parent.
+    Child(Rc<(I, f32)>),
+}
+
+impl<'a, I> Clone for Contender<'a, I> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Parent(parent) => Self::Parent(parent),
+            Self::Child(child) => Self::Child(Rc::clone(child)),
+        }
+    }
+}
+
+impl<'a, G, I: Individual<G>> Individual<G> for Contender<'a, I> {
+    fn from_chromosome(_chromosome: Chromosome<G>) -> Self {
+        unreachable!("contenders only ever get replaced")
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        match self {
+            Self::Parent(parent) => parent.chromosome(),
+            Self::Child(child) => child.0.chromosome(),
+        }
+    }
+
+    fn fitness(&self) -> f32 {
+        match self {
+            Self::Parent(parent) => parent.fitness(),
+            Self::Child(child) => child.0.fitness(),
+        }
+    }
+}
+
+enum Candidate<'a, I> {
+    Parent(&'a I),
+    Offspring(I),
+}
+
+impl<'a, I> Candidate<'a, I> {
+    fn fitness<G>(&self) -> f32
+    where
+        I: Individual<G>,
+    {
+        match self {
+            Self::Parent(parent) => parent.fitness(),
+            Self::Offspring(offspring) => offspring.fitness(),
+        }
+    }
+
+    fn into_individual(self) -> I
+    where
+        I: Clone,
+    {
+        match self {
+            Self::Parent(parent) => parent.clone(),
+            Self::Offspring(offspring) => offspring,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual(fitness: f32) -> TestIndividual {
+        TestIndividual::from_chromosome(vec![fitness].into_iter().collect())
+    }
+
+    fn fitnesses(population: &[TestIndividual]) -> Vec<f32> {
+        population.iter().map(Individual::fitness).collect()
+    }
+
+    fn replace<R: ReplacementMethod>(
+        method: &R,
+        parents: &[f32],
+        offspring: &[f32],
+    ) -> Vec<f32> {
+        let parents: Vec<_> =
+            parents.iter().map(|&fitness| individual(fitness)).collect();
+        let parents: Vec<_> = parents.iter().collect();
+        let offspring =
+            offspring.iter().map(|&fitness| individual(fitness)).collect();
+
+        fitnesses(&method.replace(&parents, offspring, parents.len()))
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_generational() {
+        let method = GenerationalReplacement::new();
+
+        assert_eq!(method.offspring_count(4), 4);
+        assert_eq!(
+            replace(&method, &[1.0, 2.0, 3.0, 4.0], &[0.5, 0.6, 0.7, 0.8]),
+            vec![0.5, 0.6, 0.7, 0.8]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_mu_plus_lambda() {
+        let method = MuPlusLambdaReplacement::new(3);
+
+        assert_eq!(method.offspring_count(4), 3);
+        assert_eq!(
+            replace(&method, &[1.0, 4.0, 2.0, 3.0], &[2.5, 5.0, 0.5]),
+            vec![5.0, 4.0, 3.0, 2.5]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_mu_comma_lambda() {
+        let method = MuCommaLambdaReplacement::new(6);
+
+        assert_eq!(method.offspring_count(4), 6);
+        assert_eq!(method.offspring_count(8), 8);
+        assert_eq!(
+            replace(
+                &method,
+                &[10.0, 40.0, 20.0, 30.0],
+                &[2.5, 5.0, 0.5, 1.0, 3.0, 4.0]
+            ),
+            vec![5.0, 4.0, 3.0, 2.5]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_steady_state() {
+        let method = SteadyStateReplacement::new(2);
+
+        assert_eq!(method.offspring_count(4), 2);
+        assert_eq!(method.offspring_count(1), 1);
+        assert_eq!(
+            replace(&method, &[1.0, 4.0, 2.0, 3.0], &[0.5, 0.6]),
+            vec![4.0, 3.0, 0.5, 0.6]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+/// Transforms fitness right before selection, e.g. to control selection
+/// pressure of fitness-proportionate methods such as
+/// [`RouletteWheelSelection`](crate::RouletteWheelSelection), or to make
+/// negative fitness usable with them.
+///
+/// Like [`NichingMethod`](crate::NichingMethod), scaling affects only
+/// selection; it's applied after niching.
+pub trait FitnessScaling {
+    /// Returns scaled fitnesses, in the same order; `generation` (counted
+    /// from zero) lets scaling change over time.
+    fn scale(&self, generation: usize, fitnesses: &[f32]) -> Vec<f32>;
+}
+
+/// Leaves fitness as it is.
+#[derive(Clone, Debug, Default)]
+pub struct NoScaling;
+
+impl NoScaling {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FitnessScaling for NoScaling {
+    fn scale(&self, _generation: usize, fitnesses: &[f32]) -> Vec<f32> {
+        fitnesses.to_vec()
+    }
+}
+
+/// Scales fitness linearly, so that an average individual gets weight 1.0
+/// and the best one gets `multiplier`.
+///
+/// Keeps selection pressure constant: early on it stops a few lucky
+/// individuals from taking over, later it keeps making a difference between
+/// individuals with nearly the same fitness. When the worst individual
+/// would get a negative weight, pressure is lowered so that it gets 0.0.
+#[derive(Clone, Debug)]
+pub struct LinearScaling {
+    /// Weight of the best individual relative to an average one:
+    /// - 1.0 = every individual gets the same weight
+    /// - 2.0 = the best individual gets twice the average weight
+    multiplier: f32,
+}
+
+impl LinearScaling {
+    pub fn new(multiplier: f32) -> Self {
+        assert!(multiplier >= 1.0);
+
+        Self { multiplier }
+    }
+}
+
+impl FitnessScaling for LinearScaling {
+    fn scale(&self, _generation: usize, fitnesses: &[f32]) -> Vec<f32> {
+        let (min, avg, max) = summary(fitnesses);
+
+        if max - avg <= 0.0 {
+            return vec![1.0; fitnesses.len()];
+        }
+
+        let scale = |fitness: f32| {
+            1.0 + (self.multiplier - 1.0) * (fitness - avg) / (max - avg)
+        };
+
+        if scale(min) >= 0.0 {
+            fitnesses.iter().map(|&fitness| scale(fitness)).collect()
+        } else {
+            fitnesses
+                .iter()
+                .map(|&fitness| (fitness - min) / (avg - min))
+                .collect()
+        }
+    }
+}
+
+/// Subtracts `mean - c * standard deviation` from fitness, truncating
+/// results below zero.
+///
+/// Individuals worse than `c` standard deviations below the mean never get
+/// selected.
+#[derive(Clone, Debug)]
+pub struct SigmaTruncation {
+    /// Number of standard deviations below the mean that still get
+    /// selected, usually between 1.0 and 3.0.
+    c: f32,
+}
+
+impl SigmaTruncation {
+    pub fn new(c: f32) -> Self {
+        assert!(c >= 0.0);
+
+        Self { c }
+    }
+}
+
+impl FitnessScaling for SigmaTruncation {
+    fn scale(&self, _generation: usize, fitnesses: &[f32]) -> Vec<f32> {
+        let (_, avg, _) = summary(fitnesses);
+
+        let std_dev = (fitnesses
+            .iter()
+            .map(|&fitness| (fitness - avg) * (fitness - avg))
+            .sum::<f32>()
+            / fitnesses.len() as f32)
+            .sqrt();
+
+        let offset = avg - self.c * std_dev;
+
+        fitnesses.iter().map(|&fitness| (fitness - offset).max(0.0)).collect()
+    }
+}
+
+/// Subtracts the worst fitness in the population from everyone's fitness.
+///
+/// Makes negative fitness usable with fitness-proportionate methods; the
+/// worst individual never gets selected (unless all are equally good).
+#[derive(Clone, Debug, Default)]
+pub struct Windowing;
+
+impl Windowing {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl FitnessScaling for Windowing {
+    fn scale(&self, _generation: usize, fitnesses: &[f32]) -> Vec<f32> {
+        let (min, _, _) = summary(fitnesses);
+
+        fitnesses.iter().map(|&fitness| fitness - min).collect()
+    }
+}
+
+/// Boltzmann (softmax) scaling: weights are `exp(fitness / temperature)`,
+/// with temperature decreasing over generations.
+///
+/// High temperature keeps weights close to each other (exploration), low
+/// temperature makes the best individuals dominate (exploitation).
+#[derive(Clone, Debug)]
+pub struct BoltzmannScaling {
+    initial_temperature: f32,
+    /// How much the temperature decreases every generation:
+    /// - 1.0 = temperature stays the same
+    /// - 0.9 = temperature drops by 10% every generation
+    decay: f32,
+    /// Temperature never drops below this value.
+    min_temperature: f32,
+}
+
+impl BoltzmannScaling {
+    pub fn new(initial_temperature: f32, decay: f32) -> Self {
+        assert!(initial_temperature > 0.0);
+        assert!(decay > 0.0 && decay <= 1.0);
+
+        Self { initial_temperature, decay, min_temperature: 0.01 }
+    }
+
+    pub fn with_min_temperature(mut self, min_temperature: f32) -> Self {
+        assert!(min_temperature > 0.0);
+
+        self.min_temperature = min_temperature;
+        self
+    }
+
+    /// Returns temperature used in given generation.
+    pub fn temperature(&self, generation: usize) -> f32 {
+        (self.initial_temperature * self.decay.powi(generation as i32))
+            .max(self.min_temperature)
+    }
+}
+
+impl FitnessScaling for BoltzmannScaling {
+    fn scale(&self, generation: usize, fitnesses: &[f32]) -> Vec<f32> {
+        let (_, _, max) = summary(fitnesses);
+        let temperature = self.temperature(generation);
+
+        // Subtracting the maximum doesn't change proportions between
+        // weights, but keeps `exp()` from overflowing
+        fitnesses
+            .iter()
+            .map(|&fitness| ((fitness - max) / temperature).exp())
+            .collect()
+    }
+}
+
+/// Returns the minimum, mean and maximum of given fitnesses.
+fn summary(fitnesses: &[f32]) -> (f32, f32, f32) {
+    let min = fitnesses.iter().cloned().fold(f32::INFINITY, f32::min);
+    let max = fitnesses.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let avg = fitnesses.iter().sum::<f32>() / fitnesses.len() as f32;
+
+    (min, avg, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const FITNESSES: &[f32] = &[1.0, 2.0, 3.0, 6.0];
+
+    #[test]
+    fn test_no_scaling() {
+        let actual = NoScaling::new().scale(0, FITNESSES);
+
+        assert_relative_eq!(actual.as_slice(), FITNESSES);
+    }
+
+    #[test]
+    fn test_linear_scaling() {
+        // Mean is 3.0, so 3.0 gets 1.0 and 6.0 gets 1.5
+        let actual = LinearScaling::new(1.5).scale(0, FITNESSES);
+        let expected = vec![2.0 / 3.0, 5.0 / 6.0, 1.0, 1.5];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+
+        // With 3.0, 1.0 would get a negative weight, so pressure gets
+        // lowered
+        let actual = LinearScaling::new(3.0).scale(0, FITNESSES);
+        let expected = vec![0.0, 0.5, 1.0, 2.5];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+
+        // Works for negative fitness as well
+        let actual =
+            LinearScaling::new(1.5).scale(0, &[-6.0, -3.0, -2.0, -1.0]);
+        let expected = vec![0.25, 1.0, 1.25, 1.5];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+
+        let actual = LinearScaling::new(2.0).scale(0, &[4.0, 4.0]);
+        let expected = vec![1.0, 1.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_sigma_truncation() {
+        // Mean is 3.0 and standard deviation is ~1.87
+        let actual = SigmaTruncation::new(1.0).scale(0, FITNESSES);
+        let expected = vec![0.0, 0.8708287, 1.8708287, 4.8708287];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_windowing() {
+        let actual = Windowing::new().scale(0, &[-1.0, -3.0, 2.0]);
+        let expected = vec![2.0, 0.0, 5.0];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_boltzmann_scaling() {
+        let method = BoltzmannScaling::new(2.0, 0.5).with_min_temperature(0.5);
+
+        assert_relative_eq!(method.temperature(0), 2.0);
+        assert_relative_eq!(method.temperature(1), 1.0);
+        assert_relative_eq!(method.temperature(5), 0.5);
+
+        let actual = method.scale(1, FITNESSES);
+        let expected: Vec<_> =
+            FITNESSES.iter().map(|fitness| (fitness - 6.0f32).exp()).collect();
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+
+        // Lower temperature, stronger preference for the best individual
+        let hot = method.scale(0, FITNESSES);
+        let cold = method.scale(5, FITNESSES);
+
+        assert!(cold[2] / cold[3] < hot[2] / hot[3]);
+    }
+}
@@ -1,78 +1,385 @@
 use crate::*;
+use rand::distributions::WeightedIndex;
 use rand::seq::SliceRandom;
 
 pub trait SelectionMethod {
-    fn select<'a, I: Individual>(
+    fn select<'a, G, I: Individual<G>>(
         &self,
         rng: &mut dyn RngCore,
         population: &'a [I],
     ) -> &'a I;
+
+    /// Selects `count` individuals at once.
+    ///
+    /// Methods that pick a whole mating pool in one go (e.g.
+    /// [`StochasticUniversalSampling`]) or that have to pre-process the
+    /// population (e.g. [`RankSelection`]) override this; others just call
+    /// [`SelectionMethod::select`] repeatedly.
+    fn select_many<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I> {
+        (0..count).map(|_| self.select(rng, population)).collect()
+    }
+
+    /// Works like [`SelectionMethod::select_many`], but returns an error
+    /// instead of panicking when the population can't be selected from.
+    fn try_select_many<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Result<Vec<&'a I>, SelectionError> {
+        if population.is_empty() {
+            return Err(SelectionError::EmptyPopulation);
+        }
+
+        Ok(self.select_many(rng, population, count))
+    }
+}
+
+/// Error returned by [`SelectionMethod::try_select_many`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectionError {
+    /// Population has no individuals.
+    EmptyPopulation,
+    /// Fitness-proportionate method got an individual with negative
+    /// fitness; see [`FitnessScaling`] for ways of shifting fitness.
+    NegativeFitness { fitness: f32 },
+    /// Fitness is NaN or infinite.
+    InvalidFitness { fitness: f32 },
+    /// Population has fewer individuals than there are elites to keep.
+    TooManyElites { elitism: usize, population: usize },
+}
+
+impl std::fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyPopulation => write!(f, "got an empty population"),
+            Self::NegativeFitness { fitness } => write!(
+                f,
+                "got an individual with negative fitness ({})",
+                fitness
+            ),
+            Self::InvalidFitness { fitness } => {
+                write!(
+                    f,
+                    "got an individual with invalid fitness ({})",
+                    fitness
+                )
+            }
+            Self::TooManyElites { elitism, population } => write!(
+                f,
+                "got a population of {} individuals, but {} elites to keep",
+                population, elitism
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SelectionError {}
+
+/// Returns fitnesses of given population as selection weights of
+/// fitness-proportionate methods.
+///
+/// When all fitnesses are zero (e.g. because no individual achieved
+/// anything yet), every individual gets the same weight.
+fn proportionate_weights<G, I: Individual<G>>(
+    population: &[I],
+) -> Result<Vec<f32>, SelectionError> {
+    if population.is_empty() {
+        return Err(SelectionError::EmptyPopulation);
+    }
+
+    let weights = population
+        .iter()
+        .map(|individual| {
+            let fitness = individual.fitness();
+
+            if !fitness.is_finite() {
+                Err(SelectionError::InvalidFitness { fitness })
+            } else if fitness < 0.0 {
+                Err(SelectionError::NegativeFitness { fitness })
+            } else {
+                Ok(fitness)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if weights.iter().all(|&weight| weight == 0.0) {
+        Ok(vec![1.0; weights.len()])
+    } else {
+        Ok(weights)
+    }
 }
 
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RouletteWheelSelection;
 
 impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a, I: Individual>(
+    fn select<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+    ) -> &'a I {
+        self.select_many(rng, population, 1)[0]
+    }
+
+    fn select_many<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I> {
+        match self.try_select_many(rng, population, count) {
+            Ok(selected) => selected,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    fn try_select_many<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Result<Vec<&'a I>, SelectionError> {
+        let weights = WeightedIndex::new(proportionate_weights(population)?)
+            .expect("got invalid fitness weights");
+
+        Ok((0..count).map(|_| &population[weights.sample(rng)]).collect())
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentSelection {
+    /// Number of individuals (drawn with replacement) taking part in a
+    /// single tournament:
+    /// - 1 = no selection pressure at all (pure random choice)
+    /// - larger = stronger selection pressure
+    size: usize,
+    /// Probability of the best contestant winning the tournament; if it
+    /// doesn't, the second best wins with the same probability and so on:
+    /// - 1.0 = the best contestant always wins
+    /// - 0.5 = weaker contestants stand a fair chance
+    probability: f32,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize, probability: f32) -> Self {
+        assert!(size > 0);
+        assert!((0.0..=1.0).contains(&probability));
+
+        Self { size, probability }
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select<'a, G, I: Individual<G>>(
         &self,
         rng: &mut dyn RngCore,
         population: &'a [I],
     ) -> &'a I {
         assert!(!population.is_empty());
 
-        population
-            .choose_weighted(rng, |individual| individual.fitness())
-            .expect("got an empty population")
+        let mut contestants: Vec<_> = (0..self.size)
+            .map(|_| population.choose(rng).expect("got an empty population"))
+            .collect();
+
+        // Only the order of fitnesses matters, so their sign and scale don't
+        contestants.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        let last = contestants.len() - 1;
+
+        contestants
+            .iter()
+            .enumerate()
+            .find(|(idx, _)| {
+                *idx == last || rng.gen_bool(self.probability as f64)
+            })
+            .map(|(_, contestant)| *contestant)
+            .unwrap()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rand::SeedableRng;
-    use rand_chacha::ChaCha8Rng;
-    use std::collections::BTreeMap;
-    use std::iter::FromIterator;
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Ranking {
+    /// The best individual gets `pressure` times the average probability of
+    /// being selected and the worst one gets `2.0 - pressure` times that:
+    /// - 1.0 = every individual is equally likely to be selected
+    /// - 2.0 = the worst individual is never selected
+    Linear { pressure: f32 },
+    /// Every individual is `base` times less likely to be selected than the
+    /// next better one:
+    /// - close to 0.0 = the best individual is almost always selected
+    /// - close to 1.0 = every individual is almost equally likely selected
+    Exponential { base: f32 },
+}
 
-    #[cfg(test)]
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum TestIndividual {
-        WithChromosome { chromosome: Chromosome },
-        WithFitness { fitness: f32 },
+/// Selects individuals with probabilities depending only on their rank
+/// within the population and not on the actual fitness values.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RankSelection {
+    ranking: Ranking,
+}
+
+impl RankSelection {
+    pub fn linear(pressure: f32) -> Self {
+        assert!((1.0..=2.0).contains(&pressure));
+
+        Self { ranking: Ranking::Linear { pressure } }
     }
 
-    #[cfg(test)]
-    impl TestIndividual {
-        pub fn new(fitness: f32) -> Self {
-            Self::WithFitness { fitness }
+    pub fn exponential(base: f32) -> Self {
+        assert!(base > 0.0 && base < 1.0);
+
+        Self { ranking: Ranking::Exponential { base } }
+    }
+
+    /// Returns selection weights of individuals, in population order.
+    fn weights<G, I: Individual<G>>(&self, population: &[I]) -> Vec<f32> {
+        let mut order: Vec<_> = (0..population.len()).collect();
+
+        order.sort_by(|&a, &b| {
+            population[a].fitness().total_cmp(&population[b].fitness())
+        });
+
+        let len = population.len() as f32;
+        let mut weights = vec![0.0; population.len()];
+
+        // `rank` goes from 0 (the worst individual) to `len - 1` (the best)
+        for (rank, idx) in order.into_iter().enumerate() {
+            let rank = rank as f32;
+
+            weights[idx] = match self.ranking {
+                Ranking::Linear { pressure } if len > 1.0 => {
+                    (2.0 - pressure)
+                        + 2.0 * (pressure - 1.0) * rank / (len - 1.0)
+                }
+                Ranking::Linear { .. } => 1.0,
+                Ranking::Exponential { base } => base.powf(len - 1.0 - rank),
+            };
         }
+
+        weights
     }
+}
 
-    #[cfg(test)]
-    impl Individual for TestIndividual {
-        fn from_chromosome(chromosome: Chromosome) -> Self {
-            Self::WithChromosome { chromosome }
+impl SelectionMethod for RankSelection {
+    fn select<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+    ) -> &'a I {
+        self.select_many(rng, population, 1)[0]
+    }
+
+    fn select_many<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I> {
+        assert!(!population.is_empty());
+
+        let weights = WeightedIndex::new(self.weights(population))
+            .expect("got invalid rank weights");
+
+        (0..count).map(|_| &population[weights.sample(rng)]).collect()
+    }
+}
+
+/// Fitness-proportionate selection that picks the whole mating pool with a
+/// single spin of a wheel with evenly spaced pointers.
+///
+/// Compared to [`RouletteWheelSelection`], every individual is selected
+/// either ⌊n⌋ or ⌈n⌉ times, where n is its expected number of copies.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StochasticUniversalSampling;
+
+impl StochasticUniversalSampling {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SelectionMethod for StochasticUniversalSampling {
+    fn select<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+    ) -> &'a I {
+        self.select_many(rng, population, 1)[0]
+    }
+
+    fn select_many<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I> {
+        match self.try_select_many(rng, population, count) {
+            Ok(selected) => selected,
+            Err(err) => panic!("{}", err),
         }
+    }
 
-        fn chromosome(&self) -> &Chromosome {
-            match self {
-                Self::WithChromosome { chromosome } => chromosome,
-                Self::WithFitness { .. } => {
-                    panic!("not supported for TestIndividual::WithFitness")
-                }
-            }
+    fn try_select_many<'a, G, I: Individual<G>>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Result<Vec<&'a I>, SelectionError> {
+        let fitnesses = proportionate_weights(population)?;
+
+        if count == 0 {
+            return Ok(Vec::new());
         }
 
-        fn fitness(&self) -> f32 {
-            match self {
-                Self::WithChromosome { chromosome } => chromosome.iter().sum(),
-                Self::WithFitness { fitness } => *fitness,
+        let total: f32 = fitnesses.iter().sum();
+
+        let spacing = total / count as f32;
+        let start = rng.gen_range(0.0..spacing);
+
+        let mut selected = Vec::with_capacity(count);
+        let mut idx = 0;
+        let mut cumulative = fitnesses[0];
+
+        for pointer in (0..count).map(|n| start + spacing * n as f32) {
+            // `idx` is bounded to guard against rounding errors near the
+            // wheel's end
+            while cumulative <= pointer && idx + 1 < population.len() {
+                idx += 1;
+                cumulative += fitnesses[idx];
             }
+
+            selected.push(&population[idx]);
         }
+
+        // Pointers visit the population in order, so without shuffling
+        // neighbouring individuals would keep mating with each other
+        selected.shuffle(rng);
+        Ok(selected)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeMap;
+    use std::iter::FromIterator;
 
     #[test]
     fn test_choose_weighted() {
+        #[allow(clippy::default_constructed_unit_structs)]
         let method = RouletteWheelSelection::default();
         let mut rng = ChaCha8Rng::from_seed(Default::default());
 
@@ -96,4 +403,299 @@ mod tests {
             BTreeMap::from_iter(vec![(1, 102), (2, 197), (3, 302), (4, 399)]);
         assert_eq!(actual_histogram, expected_histogram);
     }
+
+    #[test]
+    fn test_choose_weighted_with_zero_fitness() {
+        let method = RouletteWheelSelection;
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&[0.0; 4]);
+
+        // Every individual is equally likely to be selected
+        let actual_histogram = positions(
+            &population,
+            method.select_many(&mut rng, &population, 1000),
+        );
+
+        assert_eq!(actual_histogram, vec![248, 253, 270, 229]);
+    }
+
+    #[test]
+    fn test_invalid_fitness() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let roulette = RouletteWheelSelection;
+        let sus = StochasticUniversalSampling::new();
+
+        assert_eq!(
+            roulette.try_select_many(&mut rng, &population(&[1.0, -2.0]), 1),
+            Err(SelectionError::NegativeFitness { fitness: -2.0 })
+        );
+
+        assert!(matches!(
+            sus.try_select_many(&mut rng, &population(&[1.0, f32::NAN]), 1),
+            Err(SelectionError::InvalidFitness { fitness }) if fitness.is_nan()
+        ));
+
+        assert_eq!(
+            sus.try_select_many(&mut rng, &population(&[]), 1),
+            Err(SelectionError::EmptyPopulation)
+        );
+
+        assert_eq!(
+            TournamentSelection::new(2, 1.0).try_select_many(
+                &mut rng,
+                &population(&[]),
+                1
+            ),
+            Err(SelectionError::EmptyPopulation)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "got an individual with negative fitness (-2)")]
+    fn test_negative_fitness_panics() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        RouletteWheelSelection.select(&mut rng, &population(&[-2.0]));
+    }
+
+    /// Returns how many times each individual of `population` got selected.
+    fn positions(
+        population: &[TestIndividual],
+        selected: Vec<&TestIndividual>,
+    ) -> Vec<usize> {
+        let mut histogram = vec![0; population.len()];
+
+        for selected in selected {
+            let idx = population
+                .iter()
+                .position(|individual| std::ptr::eq(individual, selected))
+                .unwrap();
+
+            histogram[idx] += 1;
+        }
+
+        histogram
+    }
+
+    fn population(fitnesses: &[f32]) -> Vec<TestIndividual> {
+        fitnesses.iter().map(|&fitness| TestIndividual::new(fitness)).collect()
+    }
+
+    fn histogram<S: SelectionMethod>(
+        method: &S,
+        population: &[TestIndividual],
+    ) -> BTreeMap<i32, usize> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut histogram = BTreeMap::default();
+
+        for _ in 0..1000 {
+            let fitness = method.select(&mut rng, population).fitness();
+            *histogram.entry(fitness as i32).or_default() += 1;
+        }
+
+        histogram
+    }
+
+    mod tournament {
+        use super::*;
+
+        #[test]
+        fn deterministic() {
+            let method = TournamentSelection::new(2, 1.0);
+            let population = population(&[1.0, 3.0, 2.0, 4.0]);
+
+            let expected_histogram = BTreeMap::from_iter(vec![
+                (1, 56),
+                (2, 217),
+                (3, 315),
+                (4, 412),
+            ]);
+            assert_eq!(histogram(&method, &population), expected_histogram);
+        }
+
+        #[test]
+        fn probabilistic() {
+            let method = TournamentSelection::new(2, 0.75);
+            let population = population(&[1.0, 3.0, 2.0, 4.0]);
+
+            let expected_histogram = BTreeMap::from_iter(vec![
+                (1, 161),
+                (2, 222),
+                (3, 284),
+                (4, 333),
+            ]);
+            assert_eq!(histogram(&method, &population), expected_histogram);
+        }
+
+        #[test]
+        fn larger_tournament() {
+            let method = TournamentSelection::new(4, 1.0);
+            let population = population(&[1.0, 3.0, 2.0, 4.0]);
+
+            let expected_histogram =
+                BTreeMap::from_iter(vec![(1, 4), (2, 67), (3, 276), (4, 653)]);
+            assert_eq!(histogram(&method, &population), expected_histogram);
+        }
+
+        #[test]
+        fn negative_fitness() {
+            let method = TournamentSelection::new(2, 1.0);
+            let population = population(&[-1.0, -3.0, -2.0, -4.0]);
+
+            let expected_histogram = BTreeMap::from_iter(vec![
+                (-4, 62),
+                (-3, 204),
+                (-2, 309),
+                (-1, 425),
+            ]);
+            assert_eq!(histogram(&method, &population), expected_histogram);
+        }
+
+        #[test]
+        fn zero_fitness() {
+            let method = TournamentSelection::new(2, 1.0);
+            let population = population(&[0.0, 0.0, 0.0, 0.0]);
+
+            let expected_histogram = BTreeMap::from_iter(vec![(0, 1000)]);
+            assert_eq!(histogram(&method, &population), expected_histogram);
+        }
+    }
+
+    mod rank {
+        use super::*;
+
+        #[test]
+        fn linear() {
+            let method = RankSelection::linear(2.0);
+            let population = population(&[1.0, 3.0, 2.0, 4.0]);
+
+            let expected_histogram =
+                BTreeMap::from_iter(vec![(2, 174), (3, 327), (4, 499)]);
+            assert_eq!(histogram(&method, &population), expected_histogram);
+        }
+
+        #[test]
+        fn linear_without_pressure() {
+            let method = RankSelection::linear(1.0);
+            let population = population(&[1.0, 3.0, 2.0, 4.0]);
+
+            let expected_histogram = BTreeMap::from_iter(vec![
+                (1, 248),
+                (2, 270),
+                (3, 253),
+                (4, 229),
+            ]);
+            assert_eq!(histogram(&method, &population), expected_histogram);
+        }
+
+        #[test]
+        fn exponential() {
+            let method = RankSelection::exponential(0.5);
+            let population = population(&[1.0, 3.0, 2.0, 4.0]);
+
+            let expected_histogram = BTreeMap::from_iter(vec![
+                (1, 72),
+                (2, 148),
+                (3, 255),
+                (4, 525),
+            ]);
+            assert_eq!(histogram(&method, &population), expected_histogram);
+        }
+
+        #[test]
+        fn scale_invariance() {
+            let method = RankSelection::linear(1.5);
+            let population_a = population(&[1.0, 3.0, 2.0, 4.0]);
+            let population_b = population(&[-10.0, 3000.0, 0.0, 40000.0]);
+
+            let histogram_a: Vec<_> =
+                histogram(&method, &population_a).into_values().collect();
+            let histogram_b: Vec<_> =
+                histogram(&method, &population_b).into_values().collect();
+
+            assert_eq!(histogram_a, histogram_b);
+        }
+    }
+
+    mod stochastic_universal_sampling {
+        use super::*;
+
+        #[test]
+        fn select() {
+            let method = StochasticUniversalSampling::new();
+            let population = population(&[1.0, 3.0, 2.0, 4.0]);
+
+            let expected_histogram = BTreeMap::from_iter(vec![
+                (1, 102),
+                (2, 197),
+                (3, 302),
+                (4, 399),
+            ]);
+            assert_eq!(histogram(&method, &population), expected_histogram);
+        }
+
+        #[test]
+        fn select_many() {
+            let method = StochasticUniversalSampling::new();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population(&[1.0, 3.0, 0.0, 2.0, 4.0]);
+
+            // With as many pointers as total fitness, every individual gets
+            // selected exactly as many times as its fitness
+            for _ in 0..100 {
+                let actual_histogram = method
+                    .select_many(&mut rng, &population, 10)
+                    .into_iter()
+                    .fold(BTreeMap::default(), |mut histogram, individual| {
+                        *histogram
+                            .entry(individual.fitness() as i32)
+                            .or_default() += 1;
+
+                        histogram
+                    });
+
+                let expected_histogram =
+                    BTreeMap::from_iter(vec![(1, 1), (2, 2), (3, 3), (4, 4)]);
+                assert_eq!(actual_histogram, expected_histogram);
+            }
+        }
+
+        #[test]
+        fn select_many_shuffles() {
+            let method = StochasticUniversalSampling::new();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population(&[1.0; 20]);
+
+            let actual: Vec<_> = method
+                .select_many(&mut rng, &population, 20)
+                .into_iter()
+                .map(|selected| {
+                    population
+                        .iter()
+                        .position(|individual| {
+                            std::ptr::eq(individual, selected)
+                        })
+                        .unwrap()
+                })
+                .collect();
+
+            assert_ne!(actual, (0..20).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn select_many_with_zero_fitness() {
+            let method = StochasticUniversalSampling::new();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population(&[0.0; 4]);
+
+            let actual_histogram = positions(
+                &population,
+                method.select_many(&mut rng, &population, 8),
+            );
+
+            assert_eq!(actual_histogram, vec![2, 2, 2, 2]);
+        }
+    }
 }
@@ -0,0 +1,150 @@
+use crate::*;
+
+/// Summary of a population's fitness and genetic diversity.
+#[derive(Clone, Debug)]
+pub struct Statistics {
+    min_fitness: f32,
+    max_fitness: f32,
+    mean_fitness: f32,
+    median_fitness: f32,
+    std_dev_fitness: f32,
+    diversity: f32,
+}
+
+impl Statistics {
+    pub fn new<G: Gene, I: Individual<G>>(population: &[I]) -> Self {
+        assert!(!population.is_empty());
+
+        let mut fitnesses: Vec<_> =
+            population.iter().map(Individual::fitness).collect();
+
+        fitnesses.sort_by(|a, b| a.total_cmp(b));
+
+        let len = fitnesses.len();
+        let mean_fitness = mean(fitnesses.iter().cloned());
+
+        let median_fitness = if len % 2 == 0 {
+            (fitnesses[len / 2 - 1] + fitnesses[len / 2]) / 2.0
+        } else {
+            fitnesses[len / 2]
+        };
+
+        let std_dev_fitness = std_dev(fitnesses.iter().cloned());
+
+        let genes = population[0].chromosome().len();
+
+        // Average of per-gene standard deviations across the population
+        let diversity =
+            if genes == 0 {
+                0.0
+            } else {
+                mean((0..genes).map(|gene| {
+                    std_dev(population.iter().map(|individual| {
+                        individual.chromosome()[gene].to_f32()
+                    }))
+                }))
+            };
+
+        Self {
+            min_fitness: fitnesses[0],
+            max_fitness: fitnesses[len - 1],
+            mean_fitness,
+            median_fitness,
+            std_dev_fitness,
+            diversity,
+        }
+    }
+
+    pub fn min_fitness(&self) -> f32 {
+        self.min_fitness
+    }
+
+    pub fn max_fitness(&self) -> f32 {
+        self.max_fitness
+    }
+
+    pub fn mean_fitness(&self) -> f32 {
+        self.mean_fitness
+    }
+
+    pub fn median_fitness(&self) -> f32 {
+        self.median_fitness
+    }
+
+    pub fn std_dev_fitness(&self) -> f32 {
+        self.std_dev_fitness
+    }
+
+    /// Average standard deviation of genes at the same position:
+    /// - 0.0 = all chromosomes are identical
+    /// - larger = chromosomes are more spread out
+    pub fn diversity(&self) -> f32 {
+        self.diversity
+    }
+}
+
+fn mean(values: impl Iterator<Item = f32>) -> f32 {
+    let (sum, count) =
+        values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+
+    sum / count as f32
+}
+
+fn std_dev(values: impl Iterator<Item = f32> + Clone) -> f32 {
+    let avg = mean(values.clone());
+
+    mean(values.map(|value| (value - avg) * (value - avg))).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::from_chromosome(genes.iter().cloned().collect())
+    }
+
+    #[test]
+    fn test_statistics() {
+        let population = vec![
+            individual(&[1.0, 2.0]),
+            individual(&[3.0, 6.0]),
+            individual(&[0.0, 2.0]),
+            individual(&[1.0, 4.0]),
+        ];
+
+        let stats = Statistics::new(&population);
+
+        assert_relative_eq!(stats.min_fitness(), 2.0);
+        assert_relative_eq!(stats.max_fitness(), 9.0);
+        assert_relative_eq!(stats.mean_fitness(), 19.0 / 4.0);
+        assert_relative_eq!(stats.median_fitness(), 4.0);
+        assert_relative_eq!(stats.std_dev_fitness(), 2.6809513);
+        assert_relative_eq!(
+            stats.diversity(),
+            (1.0897247 + 1.6583124) / 2.0,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn test_statistics_of_odd_population() {
+        let population =
+            vec![individual(&[5.0]), individual(&[1.0]), individual(&[3.0])];
+
+        let stats = Statistics::new(&population);
+
+        assert_relative_eq!(stats.median_fitness(), 3.0);
+    }
+
+    #[test]
+    fn test_statistics_of_uniform_population() {
+        let population = vec![individual(&[1.0, -1.0]); 3];
+
+        let stats = Statistics::new(&population);
+
+        assert_relative_eq!(stats.std_dev_fitness(), 0.0);
+        assert_relative_eq!(stats.diversity(), 0.0);
+    }
+}
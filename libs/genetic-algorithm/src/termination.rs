@@ -0,0 +1,227 @@
+use crate::*;
+use std::time::Duration;
+
+/// Condition telling [`Evolution`] when to stop.
+///
+/// Conditions compose with [`Termination::or`] and [`Termination::and`],
+/// e.g. "stop after 1000 generations, or once fitness stops improving for
+/// 50 generations".
+#[derive(Clone, Debug, PartialEq)]
+pub enum Termination {
+    /// Stops after given number of generations.
+    MaxGenerations(usize),
+    /// Stops once the fittest individual reaches given fitness.
+    TargetFitness(f32),
+    /// Stops once the best fitness hasn't improved for given number of
+    /// generations.
+    Stagnation(usize),
+    /// Stops once given wall-clock time passes.
+    ///
+    /// Note that time is measured with [`std::time::Instant`], which isn't
+    /// available on `wasm32-unknown-unknown`; [`Evolution`] only reads the
+    /// clock when the condition contains a time limit.
+    TimeLimit(Duration),
+    /// Stops once population's [`Statistics::diversity`] drops to given
+    /// value or below.
+    DiversityCollapse(f32),
+    /// Stops once any of given conditions is met.
+    Any(Vec<Termination>),
+    /// Stops once all of given conditions are met.
+    All(Vec<Termination>),
+}
+
+impl Termination {
+    pub fn or(self, other: Self) -> Self {
+        match self {
+            Self::Any(mut conditions) => {
+                conditions.push(other);
+                Self::Any(conditions)
+            }
+            this => Self::Any(vec![this, other]),
+        }
+    }
+
+    pub fn and(self, other: Self) -> Self {
+        match self {
+            Self::All(mut conditions) => {
+                conditions.push(other);
+                Self::All(conditions)
+            }
+            this => Self::All(vec![this, other]),
+        }
+    }
+
+    /// Tells whether this condition depends on the elapsed time, i.e.
+    /// whether it is or contains a [`Termination::TimeLimit`].
+    pub fn has_time_limit(&self) -> bool {
+        match self {
+            Self::TimeLimit(_) => true,
+            Self::Any(conditions) | Self::All(conditions) => {
+                conditions.iter().any(Self::has_time_limit)
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks whether evolution should stop.
+    ///
+    /// `history` contains statistics of every generation so far, starting
+    /// with the initial population, so it's never empty; `elapsed` is the
+    /// time evolution has been running for.
+    pub fn is_met(&self, history: &[Statistics], elapsed: Duration) -> bool {
+        assert!(!history.is_empty());
+
+        let current = &history[history.len() - 1];
+
+        match self {
+            Self::MaxGenerations(generations) => history.len() > *generations,
+
+            Self::TargetFitness(fitness) => current.max_fitness() >= *fitness,
+
+            Self::Stagnation(generations) => {
+                if history.len() <= *generations {
+                    return false;
+                }
+
+                let (before, after) =
+                    history.split_at(history.len() - generations);
+
+                best_fitness(after) <= best_fitness(before)
+            }
+
+            Self::TimeLimit(limit) => elapsed >= *limit,
+
+            Self::DiversityCollapse(diversity) => {
+                current.diversity() <= *diversity
+            }
+
+            Self::Any(conditions) => conditions
+                .iter()
+                .any(|condition| condition.is_met(history, elapsed)),
+
+            Self::All(conditions) => conditions
+                .iter()
+                .all(|condition| condition.is_met(history, elapsed)),
+        }
+    }
+}
+
+fn best_fitness(history: &[Statistics]) -> f32 {
+    history.iter().map(Statistics::max_fitness).fold(f32::MIN, f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns history of populations consisting of given individuals;
+    /// each individual has a single gene equal to its fitness.
+    fn history(generations: &[&[f32]]) -> Vec<Statistics> {
+        generations
+            .iter()
+            .map(|fitnesses| {
+                let population: Vec<TestIndividual> = fitnesses
+                    .iter()
+                    .map(|&fitness| {
+                        TestIndividual::from_chromosome(
+                            vec![fitness].into_iter().collect(),
+                        )
+                    })
+                    .collect();
+
+                Statistics::new(&population)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_max_generations() {
+        let condition = Termination::MaxGenerations(2);
+
+        assert!(!condition.is_met(&history(&[&[1.0], &[2.0]]), Duration::ZERO));
+        assert!(condition
+            .is_met(&history(&[&[1.0], &[2.0], &[3.0]]), Duration::ZERO));
+    }
+
+    #[test]
+    fn test_target_fitness() {
+        let condition = Termination::TargetFitness(5.0);
+
+        assert!(!condition.is_met(&history(&[&[1.0, 4.0]]), Duration::ZERO));
+        assert!(condition.is_met(&history(&[&[1.0, 5.0]]), Duration::ZERO));
+    }
+
+    #[test]
+    fn test_stagnation() {
+        let condition = Termination::Stagnation(2);
+
+        // Too few generations to tell
+        assert!(!condition.is_met(&history(&[&[1.0], &[1.0]]), Duration::ZERO));
+
+        assert!(!condition
+            .is_met(&history(&[&[1.0], &[3.0], &[2.0]]), Duration::ZERO));
+
+        assert!(condition.is_met(
+            &history(&[&[1.0], &[3.0], &[2.0], &[3.0]]),
+            Duration::ZERO
+        ));
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let condition = Termination::TimeLimit(Duration::from_secs(1));
+        let history = history(&[&[1.0]]);
+
+        assert!(!condition.is_met(&history, Duration::from_millis(999)));
+        assert!(condition.is_met(&history, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_diversity_collapse() {
+        let condition = Termination::DiversityCollapse(0.0);
+
+        assert!(!condition.is_met(&history(&[&[1.0, 2.0]]), Duration::ZERO));
+        assert!(condition.is_met(&history(&[&[2.0, 2.0]]), Duration::ZERO));
+    }
+
+    #[test]
+    fn test_composition() {
+        let history = history(&[&[1.0], &[2.0]]);
+
+        let any = Termination::MaxGenerations(1)
+            .or(Termination::TargetFitness(10.0))
+            .or(Termination::TimeLimit(Duration::from_secs(1)));
+
+        assert_eq!(
+            any,
+            Termination::Any(vec![
+                Termination::MaxGenerations(1),
+                Termination::TargetFitness(10.0),
+                Termination::TimeLimit(Duration::from_secs(1)),
+            ])
+        );
+
+        assert!(any.is_met(&history, Duration::ZERO));
+
+        let all = Termination::MaxGenerations(1)
+            .and(Termination::TargetFitness(10.0));
+
+        assert!(!all.is_met(&history, Duration::ZERO));
+    }
+
+    #[test]
+    fn test_has_time_limit() {
+        let limit = Termination::TimeLimit(Duration::from_secs(1));
+
+        assert!(limit.has_time_limit());
+        assert!(!Termination::MaxGenerations(1).has_time_limit());
+
+        assert!(Termination::MaxGenerations(1)
+            .or(Termination::TargetFitness(1.0).and(limit))
+            .has_time_limit());
+
+        assert!(!Termination::MaxGenerations(1)
+            .or(Termination::Stagnation(5))
+            .has_time_limit());
+    }
+}
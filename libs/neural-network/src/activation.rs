@@ -0,0 +1,113 @@
+/// Function applied to a neuron's weighted sum before it's passed on to the
+/// next layer.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Activation {
+    /// `max(x, 0)`
+    #[default]
+    ReLU,
+    /// `x` for positive inputs, `alpha * x` otherwise
+    LeakyReLU(f32),
+    /// `1 / (1 + e^-x)`, squashes into `(0, 1)`
+    Sigmoid,
+    /// Hyperbolic tangent, squashes into `(-1, 1)`
+    Tanh,
+    /// `x`, i.e. no activation at all
+    Identity,
+    /// `x / (1 + |x|)`, squashes into `(-1, 1)`
+    Softsign,
+    /// `1` for non-negative inputs, `0` otherwise
+    Step,
+    /// User-supplied function
+    Custom(fn(f32) -> f32),
+}
+
+impl Activation {
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Self::ReLU => x.max(0.0),
+            Self::LeakyReLU(alpha) => {
+                if x >= 0.0 {
+                    x
+                } else {
+                    alpha * x
+                }
+            }
+            Self::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Self::Tanh => x.tanh(),
+            Self::Identity => x,
+            Self::Softsign => x / (1.0 + x.abs()),
+            Self::Step => {
+                if x >= 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::Custom(f) => f(x),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const INPUTS: [f32; 5] = [-2.0, -0.5, 0.0, 0.5, 2.0];
+
+    fn check(activation: Activation, expected: [f32; 5]) {
+        let actual: Vec<_> =
+            INPUTS.iter().map(|&x| activation.apply(x)).collect();
+
+        assert_relative_eq!(actual.as_slice(), expected.as_ref());
+    }
+
+    #[test]
+    fn test_relu() {
+        check(Activation::ReLU, [0.0, 0.0, 0.0, 0.5, 2.0]);
+    }
+
+    #[test]
+    fn test_leaky_relu() {
+        check(Activation::LeakyReLU(0.1), [-0.2, -0.05, 0.0, 0.5, 2.0]);
+    }
+
+    #[test]
+    fn test_sigmoid() {
+        check(
+            Activation::Sigmoid,
+            [0.11920292, 0.37754068, 0.5, 0.62245935, 0.8807971],
+        );
+    }
+
+    #[test]
+    fn test_tanh() {
+        check(
+            Activation::Tanh,
+            [-0.9640276, -0.46211717, 0.0, 0.46211717, 0.9640276],
+        );
+    }
+
+    #[test]
+    fn test_identity() {
+        check(Activation::Identity, INPUTS);
+    }
+
+    #[test]
+    fn test_softsign() {
+        check(
+            Activation::Softsign,
+            [-0.6666667, -0.33333334, 0.0, 0.33333334, 0.6666667],
+        );
+    }
+
+    #[test]
+    fn test_step() {
+        check(Activation::Step, [0.0, 0.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_custom() {
+        check(Activation::Custom(|x| x * x), [4.0, 0.25, 0.0, 0.25, 4.0]);
+    }
+}
@@ -1,3 +1,7 @@
+mod activation;
+
+pub use activation::*;
+
 use rand::prelude::*;
 
 #[derive(Debug)]
@@ -5,14 +9,17 @@ pub struct Network {
     layers: Vec<Layer>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct LayerTopology {
     pub neurons: usize,
+    /// Activation of this layer's neurons; ignored for the input layer.
+    pub activation: Activation,
 }
 
 #[derive(Debug)]
 struct Layer {
     neurons: Vec<Neuron>,
+    activation: Activation,
 }
 
 #[derive(Debug)]
@@ -26,7 +33,12 @@ impl Network {
         let layers = layers
             .windows(2)
             .map(|layers| {
-                Layer::random(rng, layers[0].neurons, layers[1].neurons)
+                Layer::random(
+                    rng,
+                    layers[0].neurons,
+                    layers[1].neurons,
+                    layers[1].activation,
+                )
             })
             .collect();
 
@@ -49,29 +61,161 @@ impl Network {
             .cloned()
     }
 
+    /// Rebuilds a network from weights produced by [`Network::weights`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layers` is not a valid topology or if `weights` doesn't
+    /// yield exactly [`Network::weight_count`] items; see
+    /// [`Network::try_from_weights`] for a fallible version.
     pub fn from_weights(
         layers: &[LayerTopology],
         weights: impl IntoIterator<Item = f32>,
     ) -> Self {
-        todo!()
+        match Self::try_from_weights(layers, weights) {
+            Ok(network) => network,
+            Err(err) => panic!("{}", err),
+        }
     }
+
+    /// Rebuilds a network from weights produced by [`Network::weights`],
+    /// checking both the topology and the number of weights.
+    pub fn try_from_weights(
+        layers: &[LayerTopology],
+        weights: impl IntoIterator<Item = f32>,
+    ) -> Result<Self, NetworkError> {
+        match layers.len() {
+            0 => return Err(NetworkError::EmptyTopology),
+            1 => return Err(NetworkError::SingleLayerTopology),
+            _ => {}
+        }
+
+        if let Some(layer) = layers.iter().position(|layer| layer.neurons == 0)
+        {
+            return Err(NetworkError::EmptyLayer { layer });
+        }
+
+        let expected = Self::weight_count(layers);
+        let mut weights = weights.into_iter();
+        let taken: Vec<_> = weights.by_ref().take(expected).collect();
+
+        if taken.len() < expected {
+            return Err(NetworkError::NotEnoughWeights {
+                expected,
+                actual: taken.len(),
+            });
+        }
+
+        if weights.next().is_some() {
+            return Err(NetworkError::TooManyWeights { expected });
+        }
+
+        let mut weights = taken.into_iter();
+
+        let layers = layers
+            .windows(2)
+            .map(|layers| {
+                Layer::from_weights(
+                    layers[0].neurons,
+                    layers[1].neurons,
+                    layers[1].activation,
+                    &mut weights,
+                )
+            })
+            .collect();
+
+        Ok(Self { layers })
+    }
+
+    /// Returns the number of biases and weights a network with given
+    /// topology consists of, i.e. the length of [`Network::weights`].
+    pub fn weight_count(layers: &[LayerTopology]) -> usize {
+        layers
+            .windows(2)
+            .map(|layers| (layers[0].neurons + 1) * layers[1].neurons)
+            .sum()
+    }
+}
+
+impl LayerTopology {
+    /// Creates a layer with the default ([`Activation::ReLU`]) activation.
+    pub fn new(neurons: usize) -> Self {
+        Self { neurons, activation: Activation::default() }
+    }
+}
+
+/// Error returned by [`Network::try_from_weights`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkError {
+    /// Topology has no layers at all.
+    EmptyTopology,
+    /// Topology has only the input layer.
+    SingleLayerTopology,
+    /// Layer at given index has no neurons.
+    EmptyLayer { layer: usize },
+    /// Weights ran out before the network was complete.
+    NotEnoughWeights { expected: usize, actual: usize },
+    /// Weights remained after the network was complete; the surplus isn't
+    /// counted, since `weights` might never end.
+    TooManyWeights { expected: usize },
 }
 
+impl std::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyTopology => write!(f, "got an empty topology"),
+            Self::SingleLayerTopology => {
+                write!(f, "got a topology with only one layer")
+            }
+            Self::EmptyLayer { layer } => {
+                write!(f, "got a layer with no neurons (layer {})", layer)
+            }
+            Self::NotEnoughWeights { expected, actual } => write!(
+                f,
+                "got not enough weights (expected {}, got {})",
+                expected, actual
+            ),
+            Self::TooManyWeights { expected } => {
+                write!(f, "got too many weights (expected {})", expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
 impl Layer {
     fn random(
         rng: &mut dyn RngCore,
         input_neurons: usize,
         output_neurons: usize,
+        activation: Activation,
     ) -> Self {
         let neurons = (0..output_neurons)
             .map(|_| Neuron::random(rng, input_neurons))
             .collect();
 
-        Layer { neurons }
+        Layer { neurons, activation }
+    }
+
+    fn from_weights(
+        input_neurons: usize,
+        output_neurons: usize,
+        activation: Activation,
+        weights: &mut dyn Iterator<Item = f32>,
+    ) -> Self {
+        let neurons = (0..output_neurons)
+            .map(|_| Neuron::from_weights(input_neurons, weights))
+            .collect();
+
+        Layer { neurons, activation }
     }
 
     fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
-        self.neurons.iter().map(|neuron| neuron.propagate(&inputs)).collect()
+        self.neurons
+            .iter()
+            .map(|neuron| neuron.propagate(&inputs, self.activation))
+            .collect()
     }
 }
 
@@ -84,7 +228,20 @@ impl Neuron {
         Neuron { bias, weights }
     }
 
-    fn propagate(&self, inputs: &[f32]) -> f32 {
+    fn from_weights(
+        output_size: usize,
+        weights: &mut dyn Iterator<Item = f32>,
+    ) -> Self {
+        let bias = weights.next().expect("got not enough weights");
+
+        let weights = (0..output_size)
+            .map(|_| weights.next().expect("got not enough weights"))
+            .collect();
+
+        Neuron { bias, weights }
+    }
+
+    fn propagate(&self, inputs: &[f32], activation: Activation) -> f32 {
         assert!((inputs.len() == self.weights.len()));
 
         let output = inputs
@@ -93,7 +250,7 @@ impl Neuron {
             .map(|(input, weight)| input * weight)
             .sum::<f32>();
 
-        f32::max(self.bias + output, 0.0)
+        activation.apply(self.bias + output)
     }
 }
 
@@ -120,10 +277,211 @@ mod tests {
     fn test_neuron_propagate() {
         let neuron = Neuron { bias: 0.5, weights: vec![-0.3, 0.8] };
 
-        assert_relative_eq!(neuron.propagate(&[-10.0, -10.0]), 0.0);
         assert_relative_eq!(
-            neuron.propagate(&[0.5, 1.0]),
+            neuron.propagate(&[-10.0, -10.0], Activation::ReLU),
+            0.0
+        );
+        assert_relative_eq!(
+            neuron.propagate(&[0.5, 1.0], Activation::ReLU),
             (-0.3 * 0.5) + (0.8 * 1.0) + 0.5
         );
     }
+
+    #[test]
+    fn test_network_weights() {
+        let network = Network {
+            layers: vec![
+                Layer {
+                    neurons: vec![Neuron {
+                        bias: 0.1,
+                        weights: vec![0.2, 0.3, 0.4],
+                    }],
+                    activation: Activation::ReLU,
+                },
+                Layer {
+                    neurons: vec![Neuron { bias: 0.5, weights: vec![0.6] }],
+                    activation: Activation::ReLU,
+                },
+            ],
+        };
+
+        let actual: Vec<_> = network.weights().collect();
+        let expected = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn test_network_from_weights() {
+        let layers = &[
+            LayerTopology::new(3),
+            LayerTopology::new(2),
+            LayerTopology::new(1),
+        ];
+        let weights =
+            vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0, 1.1];
+
+        let network = Network::from_weights(layers, weights.clone());
+        let actual: Vec<_> = network.weights().collect();
+
+        assert_relative_eq!(actual.as_slice(), weights.as_slice());
+    }
+
+    #[test]
+    fn test_network_weights_roundtrip() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let layers = &[
+            LayerTopology::new(9),
+            LayerTopology::new(18),
+            LayerTopology::new(2),
+        ];
+
+        let network = Network::random(&mut rng, layers);
+        let weights: Vec<_> = network.weights().collect();
+        let restored = Network::from_weights(layers, weights.clone());
+
+        let expected: Vec<_> = weights.iter().map(|w| w.to_bits()).collect();
+        let actual: Vec<_> = restored.weights().map(|w| w.to_bits()).collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "got not enough weights")]
+    fn test_network_from_not_enough_weights() {
+        let layers = &[LayerTopology::new(3), LayerTopology::new(2)];
+
+        Network::from_weights(layers, vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "got too many weights")]
+    fn test_network_from_too_many_weights() {
+        let layers = &[LayerTopology::new(1), LayerTopology::new(1)];
+
+        Network::from_weights(layers, vec![0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn test_network_propagate() {
+        let layers = &[
+            LayerTopology::new(2),
+            LayerTopology { neurons: 2, activation: Activation::ReLU },
+            LayerTopology { neurons: 1, activation: Activation::Tanh },
+        ];
+        let weights = vec![0.5, -1.0, 0.5, -0.5, 1.0, 0.5, 0.1, -2.0, 1.0];
+        let network = Network::from_weights(layers, weights);
+
+        // hidden layer: relu(0.5 - 1.0 * 1.0 + 0.5 * 2.0) = 0.5,
+        //               relu(-0.5 + 1.0 * 1.0 + 0.5 * 2.0) = 1.5
+        // output layer: tanh(0.1 - 2.0 * 0.5 + 1.0 * 1.5) = tanh(0.6)
+        let actual = network.propagate(vec![1.0, 2.0]);
+        let expected = [0.6f32.tanh()];
+
+        assert_relative_eq!(actual.as_slice(), expected.as_ref());
+    }
+
+    #[test]
+    fn test_network_from_weights_keeps_activations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let layers = &[
+            LayerTopology::new(3),
+            LayerTopology {
+                neurons: 4,
+                activation: Activation::LeakyReLU(0.1),
+            },
+            LayerTopology { neurons: 2, activation: Activation::Tanh },
+        ];
+
+        let network = Network::random(&mut rng, layers);
+        let restored = Network::from_weights(layers, network.weights());
+        let inputs = vec![-1.0, 0.5, 2.0];
+
+        let expected = network.propagate(inputs.clone());
+        let actual = restored.propagate(inputs);
+
+        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        assert!(actual.iter().all(|output| (-1.0..=1.0).contains(output)));
+    }
+
+    #[test]
+    fn test_network_weight_count() {
+        let layers = &[
+            LayerTopology::new(9),
+            LayerTopology::new(18),
+            LayerTopology::new(2),
+        ];
+
+        assert_eq!(Network::weight_count(layers), 10 * 18 + 19 * 2);
+        assert_eq!(Network::weight_count(&[]), 0);
+    }
+
+    mod try_from_weights {
+        use super::*;
+
+        fn err(layers: &[usize], weights: usize) -> NetworkError {
+            let layers: Vec<_> = layers
+                .iter()
+                .map(|&neurons| LayerTopology::new(neurons))
+                .collect();
+
+            Network::try_from_weights(&layers, vec![0.5; weights]).unwrap_err()
+        }
+
+        #[test]
+        fn ok() {
+            let layers = &[LayerTopology::new(2), LayerTopology::new(1)];
+
+            let network =
+                Network::try_from_weights(layers, vec![0.1, 0.2, 0.3]).unwrap();
+            let actual: Vec<_> = network.weights().collect();
+
+            assert_relative_eq!(actual.as_slice(), [0.1, 0.2, 0.3].as_ref());
+        }
+
+        #[test]
+        fn empty_topology() {
+            assert_eq!(err(&[], 0), NetworkError::EmptyTopology);
+        }
+
+        #[test]
+        fn single_layer_topology() {
+            assert_eq!(err(&[3], 0), NetworkError::SingleLayerTopology);
+        }
+
+        #[test]
+        fn empty_layer() {
+            assert_eq!(
+                err(&[3, 0, 2], 2),
+                NetworkError::EmptyLayer { layer: 1 }
+            );
+        }
+
+        #[test]
+        fn not_enough_weights() {
+            assert_eq!(
+                err(&[3, 2], 7),
+                NetworkError::NotEnoughWeights { expected: 8, actual: 7 }
+            );
+        }
+
+        #[test]
+        fn too_many_weights() {
+            assert_eq!(
+                err(&[3, 2], 11),
+                NetworkError::TooManyWeights { expected: 8 }
+            );
+        }
+
+        #[test]
+        fn infinitely_many_weights() {
+            let actual = Network::try_from_weights(
+                &[LayerTopology::new(3), LayerTopology::new(2)],
+                std::iter::repeat(0.5),
+            )
+            .unwrap_err();
+
+            assert_eq!(actual, NetworkError::TooManyWeights { expected: 8 });
+        }
+    }
 }
@@ -2,29 +2,46 @@ use super::*;
 
 #[derive(Debug)]
 pub struct Animal {
-    crate position: na::Point2<f32>,
-    crate rotation: na::Rotation2<f32>,
-    crate eye: Eye,
-    crate brain: nn::Network,
-    crate speed: f32,
-    crate satiation: usize,
+    pub(crate) position: na::Point2<f32>,
+    pub(crate) rotation: na::Rotation2<f32>,
+    pub(crate) eye: Eye,
+    pub(crate) brain: nn::Network,
+    pub(crate) speed: f32,
+    pub(crate) satiation: usize,
+    /// Number of steps the animal's been alive for.
+    pub(crate) steps: usize,
+    /// Sum of speeds over all the steps.
+    pub(crate) travelled: f32,
+    /// Sum of absolute rotations (in radians) over all the steps.
+    pub(crate) turned: f32,
 }
 
 impl Animal {
     pub fn random(rng: &mut dyn RngCore) -> Self {
         let eye = Eye::default();
-        let brain = nn::Network::random(
-            rng,
-            &[
-                // the input layer
-                nn::LayerTopology { neurons: eye.cells() },
-                // the hidden layer
-                nn::LayerTopology { neurons: 2 * eye.cells() },
-                // the output layer
-                nn::LayerTopology { neurons: 2 },
-            ],
-        );
+        let brain = nn::Network::random(rng, &Self::topology(&eye));
 
+        Self::new(eye, brain, rng)
+    }
+
+    /// Creates a fresh animal whose brain is rebuilt from `chromosome`.
+    pub(crate) fn from_chromosome(
+        chromosome: ga::Chromosome,
+        rng: &mut dyn RngCore,
+    ) -> Self {
+        let eye = Eye::default();
+        let brain =
+            nn::Network::from_weights(&Self::topology(&eye), chromosome);
+
+        Self::new(eye, brain, rng)
+    }
+
+    /// Encodes the animal's brain into a chromosome.
+    pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
+        self.brain.weights().collect()
+    }
+
+    fn new(eye: Eye, brain: nn::Network, rng: &mut dyn RngCore) -> Self {
         Self {
             position: rng.gen(),
             rotation: rng.gen(),
@@ -32,9 +49,27 @@ impl Animal {
             eye,
             brain,
             satiation: 0,
+            steps: 0,
+            travelled: 0.0,
+            turned: 0.0,
         }
     }
 
+    fn topology(eye: &Eye) -> [nn::LayerTopology; 3] {
+        [
+            // the input layer
+            nn::LayerTopology::new(eye.cells()),
+            // the hidden layer
+            nn::LayerTopology {
+                neurons: 2 * eye.cells(),
+                activation: nn::Activation::ReLU,
+            },
+            // the output layer; `Tanh` lets the bird slow down and turn
+            // both ways
+            nn::LayerTopology { neurons: 2, activation: nn::Activation::Tanh },
+        ]
+    }
+
     pub fn position(&self) -> na::Point2<f32> {
         self.position
     }
@@ -46,4 +81,23 @@ impl Animal {
     pub fn speed(&self) -> f32 {
         self.speed
     }
+
+    /// Returns how far the animal moves per step, on average.
+    pub fn average_speed(&self) -> f32 {
+        if self.steps == 0 {
+            self.speed
+        } else {
+            self.travelled / self.steps as f32
+        }
+    }
+
+    /// Returns how much the animal turns per step (in radians, either way),
+    /// on average.
+    pub fn turning_rate(&self) -> f32 {
+        if self.steps == 0 {
+            0.0
+        } else {
+            self.turned / self.steps as f32
+        }
+    }
 }
@@ -1,13 +1,17 @@
 use super::*;
 
+#[derive(Clone, Debug)]
 pub struct AnimalIndividual {
     fitness: f32,
     chromosome: ga::Chromosome,
+    /// Average speed and turning rate the animal had, as reported by
+    /// [`Animal::average_speed`] and [`Animal::turning_rate`].
+    behaviour: [f32; 2],
 }
 
 impl ga::Individual for AnimalIndividual {
     fn from_chromosome(chromosome: ga::Chromosome) -> Self {
-        Self { fitness: 0.0, chromosome }
+        Self { fitness: 0.0, chromosome, behaviour: [0.0; 2] }
     }
 
     fn chromosome(&self) -> &ga::Chromosome {
@@ -19,12 +23,90 @@ impl ga::Individual for AnimalIndividual {
     }
 }
 
+impl ga::BehaviouralIndividual for AnimalIndividual {
+    fn behaviour(&self) -> Vec<f32> {
+        self.behaviour.to_vec()
+    }
+}
+
 impl AnimalIndividual {
     pub fn from_animal(animal: &Animal) -> Self {
-        Self { fitness: animal.satiation as f32, chromosome: todo!() }
+        Self {
+            fitness: animal.satiation as f32,
+            chromosome: animal.as_chromosome(),
+            behaviour: [animal.average_speed(), animal.turning_rate()],
+        }
+    }
+
+    /// Overrides fitness, e.g. with novelty.
+    pub(crate) fn set_fitness(&mut self, fitness: f32) {
+        self.fitness = fitness;
     }
 
     pub fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
-        todo!()
+        Animal::from_chromosome(self.chromosome, rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ga::{BehaviouralIndividual, Individual};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn bits(chromosome: &ga::Chromosome) -> Vec<u32> {
+        chromosome.iter().map(|gene| gene.to_bits()).collect()
+    }
+
+    #[test]
+    fn test_from_animal() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&mut rng);
+        animal.satiation = 7;
+        animal.steps = 4;
+        animal.travelled = 0.01;
+        animal.turned = 2.0;
+
+        let individual = AnimalIndividual::from_animal(&animal);
+        let expected: Vec<_> =
+            animal.brain.weights().map(|weight| weight.to_bits()).collect();
+
+        assert_eq!(individual.fitness(), 7.0);
+        assert_eq!(individual.behaviour(), vec![0.0025, 0.5]);
+        assert_eq!(bits(individual.chromosome()), expected);
+    }
+
+    #[test]
+    fn test_into_animal() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut animal = Animal::random(&mut rng);
+        animal.satiation = 7;
+
+        let individual = AnimalIndividual::from_animal(&animal);
+        let expected = bits(individual.chromosome());
+        let animal = individual.into_animal(&mut rng);
+
+        assert_eq!(animal.satiation, 0);
+        assert_eq!(bits(&animal.as_chromosome()), expected);
+    }
+
+    #[test]
+    fn test_evolved_chromosome_roundtrip() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let animal = Animal::random(&mut rng);
+
+        let chromosome: ga::Chromosome = animal
+            .as_chromosome()
+            .into_iter()
+            .enumerate()
+            .map(|(idx, gene)| gene * (idx as f32) - 0.5)
+            .collect();
+        let expected = bits(&chromosome);
+
+        let individual = AnimalIndividual::from_chromosome(chromosome);
+        let animal = individual.into_animal(&mut rng);
+
+        assert_eq!(bits(&animal.as_chromosome()), expected);
     }
 }
@@ -2,7 +2,7 @@ use super::*;
 
 #[derive(Debug)]
 pub struct Food {
-    crate position: na::Point2<f32>,
+    pub(crate) position: na::Point2<f32>,
 }
 
 impl Food {
@@ -1,5 +1,3 @@
-#![feature(crate_visibility_modifier)]
-
 mod animal;
 mod animal_individual;
 mod eye;
@@ -39,33 +37,119 @@ const ROTATION_ACCELRATION: f32 = FRAC_PI_2;
 /// How much steps have to occur before we push data into the genetic algorithm.
 const GENERATION_LENGTH: usize = 2500;
 
+/// How many of the best birds ever seen are kept in the hall of fame.
+const HALL_OF_FAME_SIZE: usize = 10;
+
+/// Into how many cells each behaviour (average speed and turning rate) is
+/// split in the MAP-Elites grid.
+const MAP_ELITES_BINS: usize = 10;
+
 #[derive(Debug)]
 pub struct Simulation {
     world: World,
-    ga: ga::GeneticAlgorithm<
-        ga::RouletteWheelSelection,
-        ga::UniformCrossover,
-        ga::GaussianMutation,
-    >,
+    optimizer: Optimizer,
     age: usize,
+    statistics: Option<ga::Statistics>,
+    hall_of_fame: ga::HallOfFame<AnimalIndividual>,
+    elites: ga::MapElites<AnimalIndividual>,
+    novelty: Option<ga::NoveltyArchive>,
 }
 
 impl Simulation {
     pub fn random(rng: &mut dyn RngCore) -> Self {
         let world = World::random(rng, 40, 60);
+        #[allow(clippy::default_constructed_unit_structs)]
         let ga = ga::GeneticAlgorithm::new(
             ga::RouletteWheelSelection::default(),
             ga::UniformCrossover::default(),
             ga::GaussianMutation::new(0.01, 0.3),
+        )
+        .with_observer(ga::Observers::new());
+
+        let hall_of_fame = ga::HallOfFame::new(HALL_OF_FAME_SIZE);
+
+        let elites = ga::MapElites::new(
+            vec![(SPEED_MIN, SPEED_MAX), (0.0, ROTATION_ACCELRATION)],
+            MAP_ELITES_BINS,
         );
 
-        Self { world, ga, age: 0 }
+        Self {
+            world,
+            optimizer: Optimizer::Genetic(ga),
+            age: 0,
+            statistics: None,
+            hall_of_fame,
+            elites,
+            novelty: None,
+        }
+    }
+
+    /// Makes birds evolve towards behaving unlike the ones seen before,
+    /// rather than towards eating more.
+    ///
+    /// Helps birds that got stuck e.g. circling in place; statistics, the
+    /// hall of fame and the MAP-Elites grid still judge birds by how much
+    /// they ate.
+    pub fn with_novelty_search(mut self, archive: ga::NoveltyArchive) -> Self {
+        self.novelty = Some(archive);
+        self
+    }
+
+    /// Makes birds evolve with differential evolution instead of the
+    /// genetic algorithm; observers registered so far are kept.
+    pub fn with_differential_evolution(
+        mut self,
+        de: ga::DifferentialEvolution,
+    ) -> Self {
+        let observers = std::mem::take(self.optimizer.observers_mut());
+
+        self.optimizer = Optimizer::Differential(de.with_observer(observers));
+        self
+    }
+
+    /// Makes birds evolve with CMA-ES instead of the genetic algorithm;
+    /// observers registered so far are kept.
+    ///
+    /// Note that with IPOP restarts enabled, every restart doubles the
+    /// number of birds.
+    pub fn with_cma_es(mut self, cma_es: ga::CmaEs) -> Self {
+        let observers = std::mem::take(self.optimizer.observers_mut());
+
+        self.optimizer =
+            Optimizer::Covariance(Box::new(cma_es.with_observer(observers)));
+        self
     }
 
     pub fn world(&self) -> &World {
         &self.world
     }
 
+    /// Returns statistics of the most recently evaluated generation (i.e. of
+    /// how much birds ate before evolving), if any.
+    pub fn statistics(&self) -> Option<&ga::Statistics> {
+        self.statistics.as_ref()
+    }
+
+    /// Returns the best birds of all generations so far, sorted from the
+    /// fittest one; use [`AnimalIndividual::into_animal`] to bring them back.
+    pub fn hall_of_fame(&self) -> &ga::HallOfFame<AnimalIndividual> {
+        &self.hall_of_fame
+    }
+
+    /// Returns the best bird of each kind of behaviour seen so far, with
+    /// behaviours described by average speed and turning rate.
+    pub fn elites(&self) -> &ga::MapElites<AnimalIndividual> {
+        &self.elites
+    }
+
+    /// Registers observer notified whenever birds evolve.
+    pub fn add_observer(
+        &mut self,
+        observer: impl ga::EvolutionObserver + 'static,
+    ) {
+        self.optimizer.observers_mut().push(observer);
+    }
+
     pub fn step(&mut self, rng: &mut dyn RngCore) {
         self.process_collisions(rng);
         self.process_brains();
@@ -82,23 +166,41 @@ impl Simulation {
         self.age = 0;
 
         // step 1: prepare birds to be sent into the genetic algorithm.
-        let current_population: Vec<_> = self
+        let mut current_population: Vec<_> = self
             .world
             .animals
             .iter()
             .map(AnimalIndividual::from_animal)
             .collect();
 
-        // step 2: evolve birds.
-        let evolved_population = self.ga.evolve(rng, &current_population);
+        // step 2: remember the best birds.
+        self.hall_of_fame.update(&current_population);
+        self.elites.update(&current_population);
+        self.statistics = Some(ga::Statistics::new(&current_population));
+
+        // step 3: with novelty search, reward birds for their novelty
+        // instead of satiation.
+        if let Some(archive) = &mut self.novelty {
+            let novelty = archive.evaluate(&current_population);
 
-        // step 3: bring birds back from the genetic algorithm.
+            for (individual, novelty) in
+                current_population.iter_mut().zip(novelty)
+            {
+                individual.set_fitness(novelty);
+            }
+        }
+
+        // step 4: evolve birds.
+        let evolved_population =
+            self.optimizer.evolve(rng, &current_population);
+
+        // step 5: bring birds back from the genetic algorithm.
         self.world.animals = evolved_population
             .into_iter()
             .map(|individual| individual.into_animal(rng))
             .collect();
 
-        // step 4: restart foods.
+        // step 6: restart foods.
         for food in &mut self.world.foods {
             food.position = rng.gen();
         }
@@ -148,6 +250,207 @@ impl Simulation {
 
             animal.rotation =
                 na::Rotation2::new(animal.rotation.angle() + rotation);
+
+            animal.steps += 1;
+            animal.travelled += animal.speed;
+            animal.turned += rotation.abs();
+        }
+    }
+}
+
+/// Algorithm birds evolve with.
+#[derive(Debug)]
+enum Optimizer {
+    Genetic(
+        ga::GeneticAlgorithm<
+            ga::RouletteWheelSelection,
+            ga::UniformCrossover,
+            ga::GaussianMutation,
+            ga::GenerationalReplacement,
+            ga::NoNiching,
+            ga::Observers,
+        >,
+    ),
+    Differential(ga::DifferentialEvolution<f32, ga::Observers>),
+    Covariance(Box<ga::CmaEs<f32, ga::Observers>>),
+}
+
+impl Optimizer {
+    fn evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[AnimalIndividual],
+    ) -> Vec<AnimalIndividual> {
+        match self {
+            Self::Genetic(ga) => ga.evolve(rng, population).0,
+            Self::Differential(de) => de.evolve(rng, population).0,
+            Self::Covariance(cma_es) => cma_es.evolve(rng, population).0,
+        }
+    }
+
+    fn observers_mut(&mut self) -> &mut ga::Observers {
+        match self {
+            Self::Genetic(ga) => ga.observer_mut(),
+            Self::Differential(de) => de.observer_mut(),
+            Self::Covariance(cma_es) => cma_es.observer_mut(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_statistics() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+
+        for _ in 0..GENERATION_LENGTH {
+            sim.step(&mut rng);
+        }
+
+        assert!(sim.statistics().is_none());
+        assert!(sim.hall_of_fame().is_empty());
+
+        sim.step(&mut rng);
+
+        let statistics = sim.statistics().expect("generation didn't evolve");
+
+        assert!(statistics.min_fitness() >= 0.0);
+        assert!(statistics.min_fitness() <= statistics.mean_fitness());
+        assert!(statistics.mean_fitness() <= statistics.max_fitness());
+        assert_eq!(sim.hall_of_fame().len(), HALL_OF_FAME_SIZE);
+        assert_eq!(
+            sim.hall_of_fame().best().map(ga::Individual::fitness),
+            Some(statistics.max_fitness())
+        );
+        assert!(sim
+            .world()
+            .animals()
+            .iter()
+            .all(|animal| animal.satiation == 0));
+    }
+
+    #[test]
+    fn test_observer() {
+        #[derive(Clone, Default)]
+        struct Counter {
+            generations: Rc<Cell<usize>>,
+        }
+
+        impl ga::EvolutionObserver for Counter {
+            fn on_generation_end(&mut self, _: usize, _: &ga::Statistics) {
+                self.generations.set(self.generations.get() + 1);
+            }
+        }
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+        let counter = Counter::default();
+
+        sim.add_observer(counter.clone());
+
+        for _ in 0..=GENERATION_LENGTH {
+            sim.step(&mut rng);
+        }
+
+        assert_eq!(counter.generations.get(), 1);
+    }
+
+    #[test]
+    fn test_differential_evolution() {
+        #[derive(Clone, Default)]
+        struct Counter {
+            trials: Rc<Cell<usize>>,
+        }
+
+        impl ga::EvolutionObserver for Counter {
+            fn on_child_created(&mut self, _: &ga::Chromosome, _: f32) {
+                self.trials.set(self.trials.get() + 1);
+            }
         }
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng);
+        let counter = Counter::default();
+
+        // Observers registered before switching the algorithm are kept
+        sim.add_observer(counter.clone());
+
+        let mut sim = sim.with_differential_evolution(
+            ga::DifferentialEvolution::new(0.5, 0.9).with_self_adaptation(),
+        );
+
+        for _ in 0..=GENERATION_LENGTH {
+            sim.step(&mut rng);
+        }
+
+        let statistics = sim.statistics().expect("generation didn't evolve");
+
+        assert!(statistics.max_fitness() > 0.0);
+        assert_eq!(sim.hall_of_fame().len(), HALL_OF_FAME_SIZE);
+        assert_eq!(sim.world().animals().len(), 40);
+        assert_eq!(counter.trials.get(), 40);
+    }
+
+    #[test]
+    fn test_cma_es() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut sim = Simulation::random(&mut rng)
+            .with_cma_es(ga::CmaEs::new(0.5).with_ipop(3));
+
+        for _ in 0..=GENERATION_LENGTH {
+            sim.step(&mut rng);
+        }
+
+        let statistics = sim.statistics().expect("generation didn't evolve");
+
+        assert!(statistics.max_fitness() > 0.0);
+        assert_eq!(sim.hall_of_fame().len(), HALL_OF_FAME_SIZE);
+        assert_eq!(sim.world().animals().len(), 40);
+    }
+
+    #[test]
+    fn test_behaviour() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut sim = Simulation::random(&mut rng)
+            .with_novelty_search(ga::NoveltyArchive::new(5, 0.0));
+
+        for _ in 0..GENERATION_LENGTH {
+            sim.step(&mut rng);
+        }
+
+        // Averages are sums of thousands of steps, so they're allowed to
+        // be a bit off due to rounding
+        let (speed_min, speed_max) = (0.99 * SPEED_MIN, 1.01 * SPEED_MAX);
+
+        assert!(sim.world().animals().iter().all(|animal| {
+            (speed_min..=speed_max).contains(&animal.average_speed())
+                && (0.0..=ROTATION_ACCELRATION).contains(&animal.turning_rate())
+        }));
+
+        assert!(sim.elites().is_empty());
+
+        sim.step(&mut rng);
+
+        let statistics = sim.statistics().expect("generation didn't evolve");
+
+        // Grid judges birds by satiation, not novelty
+        assert!(!sim.elites().is_empty());
+        assert_eq!(
+            sim.elites().best().map(ga::Individual::fitness),
+            Some(statistics.max_fitness())
+        );
+        assert!(sim
+            .world()
+            .animals()
+            .iter()
+            .all(|animal| animal.turning_rate() == 0.0));
     }
 }
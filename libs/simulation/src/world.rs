@@ -2,8 +2,8 @@ use super::*;
 
 #[derive(Debug)]
 pub struct World {
-    crate animals: Vec<Animal>,
-    crate foods: Vec<Food>,
+    pub(crate) animals: Vec<Animal>,
+    pub(crate) foods: Vec<Food>,
 }
 
 impl World {